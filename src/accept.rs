@@ -0,0 +1,249 @@
+use crate::{MediaRange, MimeType, ParseMediaTypeError};
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// A parsed HTTP `Accept` header.
+///
+/// Parses the media ranges and quality values of an `Accept` header as
+/// defined in RFC 9110 (section 12.5.1) and selects the best representation
+/// with [`Accept::negotiate`]. Accept extensions following the `q` parameter
+/// are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use mime_type::{Accept, Archive, Image, MimeType};
+///
+/// let accept: Accept = "image/webp, image/*;q=0.8, */*;q=0.1".parse().unwrap();
+/// let available = [MimeType::Image(Image::Png), MimeType::Image(Image::Webp)];
+/// assert_eq!(accept.negotiate(&available), Some(MimeType::Image(Image::Webp)));
+///
+/// let available = [MimeType::Archive(Archive::Pdf)];
+/// assert_eq!(accept.negotiate(&available), Some(MimeType::Archive(Archive::Pdf)));
+///
+/// let accept: Accept = "text/html;level=1;q=0.7".parse().unwrap();
+/// assert_eq!(accept.to_string(), "text/html; level=1; q=0.7");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accept {
+    items: Vec<AcceptItem>,
+}
+
+/// A single media range of an `Accept` header with its quality value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcceptItem {
+    range: MediaRange,
+    /// Quality in thousandths, so `q=0.8` is stored as 800.
+    quality: u16,
+}
+
+impl Accept {
+    /// Parses the value of an `Accept` header.
+    ///
+    /// Equivalent to `s.parse::<Accept>()`. Empty list elements are skipped,
+    /// and an empty header yields an `Accept` that accepts no type; callers
+    /// should treat a missing header as `*/*` themselves.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a media range or quality value is malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{Accept, Image, MimeType};
+    ///
+    /// let accept = Accept::parse("").unwrap();
+    /// assert_eq!(accept.iter().count(), 0);
+    /// assert_eq!(accept.quality(&MimeType::Image(Image::Png)), 0.0);
+    /// assert!(accept.negotiate(&[MimeType::Image(Image::Png)]).is_none());
+    /// ```
+    pub fn parse(s: &str) -> Result<Accept, ParseMediaTypeError> {
+        let mut items = split_list(s)
+            .map(AcceptItem::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        items.sort_by(|a, b| {
+            b.quality
+                .cmp(&a.quality)
+                .then_with(|| b.range.specificity().cmp(&a.range.specificity()))
+        });
+        Ok(Accept { items })
+    }
+
+    /// Returns the media ranges, highest quality first and then most
+    /// specific first.
+    pub fn iter(&self) -> impl Iterator<Item = &AcceptItem> {
+        self.items.iter()
+    }
+
+    /// Returns the quality assigned to a MIME type, between 0.0 and 1.0.
+    ///
+    /// The quality comes from the most specific matching range; types that
+    /// no range matches get 0.0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{Accept, Audio, Image, MimeType};
+    ///
+    /// let accept = Accept::parse("image/*;q=0.5, image/png").unwrap();
+    /// assert_eq!(accept.quality(&MimeType::Image(Image::Png)), 1.0);
+    /// assert_eq!(accept.quality(&MimeType::Image(Image::Gif)), 0.5);
+    /// assert_eq!(accept.quality(&MimeType::Audio(Audio::Mpeg)), 0.0);
+    /// ```
+    pub fn quality(&self, mime: &MimeType) -> f32 {
+        f32::from(self.match_for(mime).map_or(0, |(quality, _)| quality)) / 1000.0
+    }
+
+    /// Picks the best MIME type to serve from the available ones.
+    ///
+    /// Each available type gets the quality of the most specific range that
+    /// matches it. The type with the highest non-zero quality wins; ties go to
+    /// the type matched by the more specific range, then to the type listed
+    /// first in `available`, so servers should list their preferred types first.
+    ///
+    /// Returns `None` if no available type is acceptable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{Accept, Document, MimeType};
+    ///
+    /// let accept = Accept::parse("application/*, application/msword;q=0").unwrap();
+    /// let available = [
+    ///     MimeType::Document(Document::Doc),
+    ///     MimeType::Document(Document::Docx),
+    /// ];
+    /// assert_eq!(accept.negotiate(&available), Some(MimeType::Document(Document::Docx)));
+    /// ```
+    pub fn negotiate(&self, available: &[MimeType]) -> Option<MimeType> {
+        let mut best: Option<(&MimeType, u16, usize)> = None;
+        for mime in available {
+            let Some((quality, specificity)) = self.match_for(mime) else {
+                continue;
+            };
+            if quality == 0 {
+                continue;
+            }
+            let better = best.is_none_or(|(_, best_quality, best_specificity)| {
+                (quality, specificity) > (best_quality, best_specificity)
+            });
+            if better {
+                best = Some((mime, quality, specificity));
+            }
+        }
+        best.map(|(mime, _, _)| mime.clone())
+    }
+
+    /// Returns the quality and specificity of the most specific range
+    /// matching `mime`, preferring the higher quality among equally
+    /// specific ranges.
+    fn match_for(&self, mime: &MimeType) -> Option<(u16, usize)> {
+        self.items
+            .iter()
+            .filter(|item| item.range.matches(mime))
+            .map(|item| (item.quality, item.range.specificity()))
+            .rev()
+            .max_by_key(|&(_, specificity)| specificity)
+    }
+}
+
+impl FromStr for Accept {
+    type Err = ParseMediaTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Accept::parse(s)
+    }
+}
+
+impl Display for Accept {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (idx, item) in self.items.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", item)?;
+        }
+        Ok(())
+    }
+}
+
+impl AcceptItem {
+    fn parse(s: &str) -> Result<AcceptItem, ParseMediaTypeError> {
+        let mut range = MediaRange::parse(s)?;
+        let q_idx = range.params().position(|(name, _)| name == "q");
+        let quality = match q_idx {
+            Some(idx) => parse_quality(&range.split_off_params(idx)[0].1)?,
+            None => 1000,
+        };
+        Ok(AcceptItem { range, quality })
+    }
+
+    /// Returns the media range, without the `q` parameter.
+    pub fn range(&self) -> &MediaRange {
+        &self.range
+    }
+
+    /// Returns the quality value, between 0.0 and 1.0.
+    pub fn quality(&self) -> f32 {
+        f32::from(self.quality) / 1000.0
+    }
+}
+
+impl Display for AcceptItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.range)?;
+        match self.quality {
+            1000 => Ok(()),
+            0 => write!(f, "; q=0"),
+            quality => {
+                let decimals = format!("{:03}", quality);
+                write!(f, "; q=0.{}", decimals.trim_end_matches('0'))
+            }
+        }
+    }
+}
+
+/// Parses a `qvalue` into thousandths.
+///
+/// ```text
+/// qvalue = ( "0" [ "." 0*3DIGIT ] ) / ( "1" [ "." 0*3("0") ] )
+/// ```
+fn parse_quality(s: &str) -> Result<u16, ParseMediaTypeError> {
+    let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+    if frac.len() > 3 || !frac.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ParseMediaTypeError::InvalidQuality);
+    }
+    let frac = format!("{:0<3}", frac).parse::<u16>().unwrap_or(0);
+    match int {
+        "0" => Ok(frac),
+        "1" if frac == 0 => Ok(1000),
+        _ => Err(ParseMediaTypeError::InvalidQuality),
+    }
+}
+
+/// Splits a comma-separated header list, ignoring commas inside quoted
+/// strings and skipping empty elements.
+fn split_list(s: &str) -> impl Iterator<Item = &str> {
+    let mut elements = Vec::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+    let mut escaped = false;
+    for (idx, c) in s.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => {
+                elements.push(&s[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    elements.push(&s[start..]);
+    elements
+        .into_iter()
+        .map(|element| element.trim_matches([' ', '\t']))
+        .filter(|element| !element.is_empty())
+}
@@ -1,86 +1,82 @@
-use crate::MimeFormat;
-use std::fmt::{self, Display, Formatter};
-
-/// Application and executable file formats.
-///
-/// Supports various executable and binary application formats.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Application {
-    /// WebAssembly binary format
-    Wasm,
-    /// Windows executable format
-    Exe,
-    /// Windows dynamic link library
-    Dll,
-    /// Executable and Linkable Format (Linux/Unix)
-    Elf,
-    /// LLVM bitcode format
-    Bc,
-    /// Mach-O binary format (macOS)
-    Mach,
-    /// Java class file
-    Class,
-    /// Dalvik executable format (Android)
-    Dex,
-    /// Optimized Dalvik executable (Android)
-    Dey,
-    /// X.509 certificate (DER encoded)
-    Der,
-    /// Object file format
-    Obj,
-}
-
-impl Display for Application {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let mime_str = match self {
-            Application::Wasm => "application/wasm",
-            Application::Exe => "application/vnd.microsoft.portable-executable",
-            Application::Dll => "application/vnd.microsoft.portable-executable",
-            Application::Elf => "application/x-executable",
-            Application::Bc => "application/llvm",
-            Application::Mach => "application/x-mach-binary",
-            Application::Class => "application/java",
-            Application::Dex => "application/vnd.android.dex",
-            Application::Dey => "application/vnd.android.dey",
-            Application::Der => "application/x-x509-ca-cert",
-            Application::Obj => "application/x-executable",
-        };
-        write!(f, "{}", mime_str)
-    }
-}
-
-impl MimeFormat for Application {
-    fn from_ext(ext: &str) -> Option<crate::MimeType> {
-        match ext {
-            "wasm" => Some(crate::MimeType::Application(Application::Wasm)),
-            "exe" => Some(crate::MimeType::Application(Application::Exe)),
-            "dll" => Some(crate::MimeType::Application(Application::Dll)),
-            "elf" => Some(crate::MimeType::Application(Application::Elf)),
-            "bc" => Some(crate::MimeType::Application(Application::Bc)),
-            "mach" => Some(crate::MimeType::Application(Application::Mach)),
-            "class" => Some(crate::MimeType::Application(Application::Class)),
-            "dex" => Some(crate::MimeType::Application(Application::Dex)),
-            "dey" => Some(crate::MimeType::Application(Application::Dey)),
-            "der" => Some(crate::MimeType::Application(Application::Der)),
-            "obj" => Some(crate::MimeType::Application(Application::Obj)),
-            _ => None,
-        }
-    }
-
-    fn from_mime(mime: &str) -> Option<crate::MimeType> {
-        match mime {
-            "application/wasm" => Some(crate::MimeType::Application(Application::Wasm)),
-            "application/vnd.microsoft.portable-executable" => {
-                Some(crate::MimeType::Application(Application::Exe))
-            }
-            "application/x-executable" => Some(crate::MimeType::Application(Application::Elf)),
-            "application/llvm" => Some(crate::MimeType::Application(Application::Bc)),
-            "application/x-mach-binary" => Some(crate::MimeType::Application(Application::Mach)),
-            "application/java" => Some(crate::MimeType::Application(Application::Class)),
-            "application/vnd.android.dex" => Some(crate::MimeType::Application(Application::Dex)),
-            "application/vnd.android.dey" => Some(crate::MimeType::Application(Application::Dey)),
-            "application/x-x509-ca-cert" => Some(crate::MimeType::Application(Application::Der)),
-            _ => None,
-        }
+mime_category! {
+    /// Application and executable file formats.
+    ///
+    /// Supports various executable and binary application formats.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::Application;
+    ///
+    /// assert_eq!(Application::Wasm.extensions(), &["wasm"]);
+    /// assert_eq!(Application::Wasm.preferred_extension(), "wasm");
+    /// ```
+    pub enum Application {
+        /// WebAssembly binary format
+        Wasm {
+            mime: "application/wasm",
+            aliases: [],
+            extensions: ["wasm"],
+        },
+        /// Windows executable format
+        Exe {
+            mime: "application/vnd.microsoft.portable-executable",
+            aliases: [],
+            extensions: ["exe"],
+        },
+        /// Windows dynamic link library
+        Dll {
+            mime: "application/vnd.microsoft.portable-executable",
+            aliases: [],
+            extensions: ["dll"],
+        },
+        /// Executable and Linkable Format (Linux/Unix)
+        Elf {
+            mime: "application/x-executable",
+            aliases: [],
+            extensions: ["elf"],
+        },
+        /// LLVM bitcode format
+        Bc {
+            mime: "application/llvm",
+            aliases: [],
+            extensions: ["bc"],
+        },
+        /// Mach-O binary format (macOS)
+        Mach {
+            mime: "application/x-mach-binary",
+            aliases: [],
+            extensions: ["mach"],
+        },
+        /// Java class file
+        Class {
+            mime: "application/java",
+            aliases: ["application/java-vm"],
+            extensions: ["class"],
+        },
+        /// Dalvik executable format (Android)
+        Dex {
+            mime: "application/vnd.android.dex",
+            aliases: [],
+            extensions: ["dex"],
+        },
+        /// Optimized Dalvik executable (Android)
+        Dey {
+            mime: "application/vnd.android.dey",
+            aliases: [],
+            extensions: ["dey"],
+        },
+        /// X.509 certificate (DER encoded)
+        Der {
+            mime: "application/x-x509-ca-cert",
+            aliases: ["application/pkix-cert"],
+            extensions: ["der"],
+        },
+        /// Object file format
+        Obj {
+            mime: "application/x-executable",
+            aliases: [],
+            extensions: ["obj"],
+        },
     }
 }
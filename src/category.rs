@@ -0,0 +1,109 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// The category of a MIME type, mirroring the variants of [`MimeType`](crate::MimeType).
+///
+/// Unlike `MimeType`, categories carry no data, so they can be used as hash
+/// keys, in allowlists and in configuration files. They display and parse as
+/// lowercase names such as `image` or `application`.
+///
+/// # Examples
+///
+/// ```
+/// use mime_type::{Category, Image, MimeType};
+///
+/// let mime = MimeType::Image(Image::Png);
+/// assert_eq!(mime.category(), Category::Image);
+///
+/// let category: Category = "image".parse().unwrap();
+/// assert_eq!(category.to_string(), "image");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Category {
+    /// Image formats
+    Image,
+    /// Video formats
+    Video,
+    /// Audio formats
+    Audio,
+    /// Archive and compressed formats
+    Archive,
+    /// E-book formats
+    Book,
+    /// Document formats
+    Document,
+    /// Font formats
+    Font,
+    /// Application and executable formats
+    Application,
+    /// Text formats
+    Text,
+    /// Valid media types that are not one of the known formats
+    Other,
+}
+
+impl Category {
+    /// Returns all categories in the order `MimeType` lookups check them.
+    pub fn all() -> &'static [Category] {
+        &[
+            Category::Image,
+            Category::Video,
+            Category::Audio,
+            Category::Archive,
+            Category::Book,
+            Category::Document,
+            Category::Font,
+            Category::Application,
+            Category::Text,
+            Category::Other,
+        ]
+    }
+
+    /// Returns the lowercase name of the category, e.g. `image`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Category::Image => "image",
+            Category::Video => "video",
+            Category::Audio => "audio",
+            Category::Archive => "archive",
+            Category::Book => "book",
+            Category::Document => "document",
+            Category::Font => "font",
+            Category::Application => "application",
+            Category::Text => "text",
+            Category::Other => "other",
+        }
+    }
+}
+
+impl Display for Category {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Category {
+    type Err = ParseCategoryError;
+
+    /// Parses a category name, ignoring ASCII case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Category::all()
+            .iter()
+            .find(|category| category.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or(ParseCategoryError)
+    }
+}
+
+/// Error returned when parsing an unknown category name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCategoryError;
+
+impl Display for ParseCategoryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown category")
+    }
+}
+
+impl Error for ParseCategoryError {}
@@ -0,0 +1,90 @@
+use crate::MimeType;
+use crate::signature::{self, HEADER_LEN};
+
+/// The state of a [`Detector`] after it was fed some bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Detection {
+    /// The content matches a known signature.
+    Detected(MimeType),
+    /// A signature may still match; the detector needs at least this many
+    /// bytes in total before it can decide.
+    NeedMore(usize),
+    /// No known signature matches.
+    Unknown,
+}
+
+/// Incremental content detection for data that arrives in chunks.
+///
+/// Feed chunks as they arrive; the detector buffers only what the signatures
+/// need and decides as soon as possible, e.g. after 8 bytes for PNG. Formats
+/// checked after TAR wait for its magic at offset 257, and ZIP and OLE
+/// documents for the whole prefix. The detector never buffers more than
+/// [`Detector::MAX_PREFIX`] bytes: once that prefix is known the result is
+/// final, and it agrees with [`MimeType::from_bytes`] on the same prefix.
+/// Call [`finish`](Detector::finish) if the stream ends while the detector
+/// still needs more bytes.
+///
+/// # Examples
+///
+/// ```
+/// use mime_type::{Detection, Detector, Image, MimeType};
+///
+/// let mut detector = Detector::new();
+/// assert_eq!(detector.feed(b"\x89PN"), Detection::NeedMore(8));
+/// assert_eq!(
+///     detector.feed(b"G\r\n\x1a\n\0\0\0\x0dIHDR"),
+///     Detection::Detected(MimeType::Image(Image::Png))
+/// );
+///
+/// // A TAR header could still follow at offset 257
+/// let mut detector = Detector::new();
+/// assert_eq!(detector.feed(b"plain text"), Detection::NeedMore(262));
+/// assert_eq!(detector.finish(), Detection::Unknown);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Detector {
+    buffer: Vec<u8>,
+    result: Option<Detection>,
+}
+
+impl Detector {
+    /// The maximum number of bytes a detector buffers.
+    pub const MAX_PREFIX: usize = HEADER_LEN;
+
+    /// Creates a detector that has not seen any bytes.
+    pub fn new() -> Detector {
+        Detector::default()
+    }
+
+    /// Feeds the next chunk of the stream.
+    ///
+    /// Once the result is `Detected` or `Unknown`, further chunks are ignored
+    /// and the same result is returned.
+    pub fn feed(&mut self, chunk: &[u8]) -> Detection {
+        if let Some(result) = &self.result {
+            return result.clone();
+        }
+        let take = chunk.len().min(HEADER_LEN - self.buffer.len());
+        self.buffer.extend_from_slice(&chunk[..take]);
+        let complete = self.buffer.len() == HEADER_LEN;
+        self.decide(complete)
+    }
+
+    /// Signals the end of the stream and returns the final result, which is
+    /// never `NeedMore`.
+    pub fn finish(&mut self) -> Detection {
+        match &self.result {
+            Some(result) => result.clone(),
+            None => self.decide(true),
+        }
+    }
+
+    fn decide(&mut self, complete: bool) -> Detection {
+        let detection = signature::detect_prefix(&self.buffer, complete);
+        if !matches!(detection, Detection::NeedMore(_)) {
+            self.buffer = Vec::new();
+            self.result = Some(detection.clone());
+        }
+        detection
+    }
+}
@@ -0,0 +1,194 @@
+use crate::{Audio, MimeType, Video};
+
+const EBML_HEADER: u32 = 0x1A45_DFA3;
+const DOC_TYPE: u32 = 0x4282;
+const DOC_TYPE_VERSION: u32 = 0x4287;
+const DOC_TYPE_READ_VERSION: u32 = 0x4285;
+const SEGMENT: u32 = 0x1853_8067;
+const TRACKS: u32 = 0x1654_AE6B;
+const TRACK_ENTRY: u32 = 0xAE;
+const TRACK_TYPE: u32 = 0x83;
+const VIDEO: u32 = 0xE0;
+const STEREO_MODE: u32 = 0x53B8;
+const CLUSTER: u32 = 0x1F43_B675;
+
+/// Matroska track type of video tracks.
+const TRACK_VIDEO: u64 = 1;
+/// Matroska track type of audio tracks.
+const TRACK_AUDIO: u64 = 2;
+
+/// The EBML header and track types of a Matroska or WebM file.
+///
+/// Both formats share the EBML magic bytes; the `DocType` of the header tells
+/// them apart. Matroska files are further told apart by their tracks: audio
+/// without video is MKA, and video with a stereo mode is MK3D.
+///
+/// Only the given bytes are parsed. Tracks are read from the segment up to
+/// the first cluster, where muxers place them.
+///
+/// # Examples
+///
+/// ```
+/// use mime_type::{Ebml, MimeType, Video};
+///
+/// let header = b"\x1A\x45\xDF\xA3\x8B\x42\x82\x84webm\x42\x87\x81\x04";
+/// let ebml = Ebml::parse(header).unwrap();
+/// assert_eq!(ebml.doc_type(), "webm");
+/// assert_eq!(ebml.doc_type_version(), 4);
+/// assert_eq!(ebml.mime_type(), Some(MimeType::Video(Video::Webm)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ebml {
+    doc_type: String,
+    doc_type_version: u64,
+    doc_type_read_version: u64,
+    track_types: Vec<u64>,
+    stereoscopic: bool,
+}
+
+impl Ebml {
+    /// Parses the EBML header and the tracks of the first segment.
+    ///
+    /// Returns `None` if the bytes do not start with an EBML header. Missing
+    /// header fields take their default values: `matroska` and version 1.
+    pub fn parse(bytes: &[u8]) -> Option<Ebml> {
+        let mut top_level = elements(bytes);
+        let (id, header) = top_level.next()?;
+        if id != EBML_HEADER {
+            return None;
+        }
+
+        let mut ebml = Ebml {
+            doc_type: String::from("matroska"),
+            doc_type_version: 1,
+            doc_type_read_version: 1,
+            track_types: Vec::new(),
+            stereoscopic: false,
+        };
+        for (id, data) in elements(header) {
+            match id {
+                DOC_TYPE => {
+                    let end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+                    ebml.doc_type = String::from_utf8_lossy(&data[..end]).into_owned();
+                }
+                DOC_TYPE_VERSION => ebml.doc_type_version = uint(data),
+                DOC_TYPE_READ_VERSION => ebml.doc_type_read_version = uint(data),
+                _ => {}
+            }
+        }
+
+        if let Some((_, segment)) = top_level.find(|(id, _)| *id == SEGMENT) {
+            let tracks = elements(segment)
+                .take_while(|(id, _)| *id != CLUSTER)
+                .filter(|(id, _)| *id == TRACKS)
+                .flat_map(|(_, tracks)| elements(tracks))
+                .filter(|(id, _)| *id == TRACK_ENTRY);
+            for (_, entry) in tracks {
+                for (id, data) in elements(entry) {
+                    match id {
+                        TRACK_TYPE => ebml.track_types.push(uint(data)),
+                        VIDEO => {
+                            ebml.stereoscopic |= elements(data)
+                                .any(|(id, data)| id == STEREO_MODE && uint(data) != 0);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Some(ebml)
+    }
+
+    /// Returns the document type, e.g. `matroska` or `webm`.
+    pub fn doc_type(&self) -> &str {
+        &self.doc_type
+    }
+
+    /// Returns the version of the document type the file was written with.
+    pub fn doc_type_version(&self) -> u64 {
+        self.doc_type_version
+    }
+
+    /// Returns the minimum document type version a reader must support.
+    pub fn doc_type_read_version(&self) -> u64 {
+        self.doc_type_read_version
+    }
+
+    /// Returns the Matroska track types, in file order: 1 for video, 2 for
+    /// audio, 17 for subtitles, etc.
+    pub fn track_types(&self) -> &[u64] {
+        &self.track_types
+    }
+
+    /// Returns `true` if a video track has a stereo 3D mode.
+    pub fn is_stereoscopic(&self) -> bool {
+        self.stereoscopic
+    }
+
+    /// Maps the document type and tracks to a MIME type, or `None` for
+    /// document types other than Matroska and WebM.
+    pub fn mime_type(&self) -> Option<MimeType> {
+        let has_video = self.track_types.contains(&TRACK_VIDEO);
+        let has_audio = self.track_types.contains(&TRACK_AUDIO);
+        let mime = match self.doc_type.as_str() {
+            "webm" => MimeType::Video(Video::Webm),
+            "matroska" if self.stereoscopic => MimeType::Video(Video::Mk3d),
+            "matroska" if has_audio && !has_video => MimeType::Audio(Audio::Mka),
+            "matroska" => MimeType::Video(Video::Mkv),
+            _ => return None,
+        };
+        Some(mime)
+    }
+}
+
+/// Reads a variable-length integer, returning its value with the length
+/// marker kept (for IDs) or removed (for sizes), and its length.
+fn vint(bytes: &[u8], keep_marker: bool) -> Option<(u64, usize)> {
+    let first = *bytes.first()?;
+    let len = first.leading_zeros() as usize + 1;
+    if len > 8 {
+        return None;
+    }
+    let marker = if keep_marker {
+        first
+    } else {
+        first & (0xFF_u16 >> len) as u8
+    };
+    let value = bytes
+        .get(1..len)?
+        .iter()
+        .fold(u64::from(marker), |value, &b| value << 8 | u64::from(b));
+    Some((value, len))
+}
+
+/// Iterates the elements in `bytes` as `(id, data)` pairs.
+///
+/// Elements extending past the end of `bytes`, or with an unknown size, are
+/// cut short, so the leading part of a file can be parsed.
+fn elements(bytes: &[u8]) -> impl Iterator<Item = (u32, &[u8])> {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let (id, id_len) = vint(bytes.get(pos..)?, true)?;
+        if id_len > 4 {
+            return None;
+        }
+        let (size, size_len) = vint(bytes.get(pos + id_len..)?, false)?;
+        let start = pos + id_len + size_len;
+        // All value bits set means the size is unknown
+        let unknown = size == (1_u64 << (7 * size_len)) - 1;
+        let end = match usize::try_from(size) {
+            Ok(size) if !unknown => start.saturating_add(size).min(bytes.len()),
+            _ => bytes.len(),
+        };
+        let data = bytes.get(start..end)?;
+        pos = end;
+        Some((id as u32, data))
+    })
+}
+
+/// Reads a big-endian unsigned integer element.
+fn uint(data: &[u8]) -> u64 {
+    data.iter()
+        .take(8)
+        .fold(0, |value, &b| value << 8 | u64::from(b))
+}
@@ -1,101 +1,106 @@
-use crate::MimeFormat;
-use std::fmt::{self, Display, Formatter};
-
-/// Image file formats.
-///
-/// Supports common image formats including raster and vector types.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Image {
-    /// JPEG image format
-    Jpeg,
-    /// PNG image format
-    Png,
-    /// GIF image format
-    Gif,
-    /// WebP image format
-    Webp,
-    /// Canon Raw 2 format
-    Cr2,
-    /// TIFF image format
-    Tif,
-    /// BMP image format
-    Bmp,
-    /// HEIF image format
-    Heif,
-    /// AVIF image format
-    Avif,
-    /// JPEG XR format
-    Jxr,
-    /// Adobe Photoshop document
-    Psd,
-    /// ICO icon format
-    Ico,
-    /// OpenRaster format
-    Ora,
-    /// DjVu document format
-    Djvu,
-}
-
-impl Display for Image {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let mime_str = match self {
-            Image::Jpeg => "image/jpeg",
-            Image::Png => "image/png",
-            Image::Gif => "image/gif",
-            Image::Webp => "image/webp",
-            Image::Cr2 => "image/x-canon-cr2",
-            Image::Tif => "image/tiff",
-            Image::Bmp => "image/bmp",
-            Image::Heif => "image/heif",
-            Image::Avif => "image/avif",
-            Image::Jxr => "image/vnd.ms-photo",
-            Image::Psd => "image/vnd.adobe.photoshop",
-            Image::Ico => "image/vnd.microsoft.icon",
-            Image::Ora => "image/openraster",
-            Image::Djvu => "image/vnd.djvu",
-        };
-        write!(f, "{}", mime_str)
-    }
-}
-
-impl MimeFormat for Image {
-    fn from_ext(ext: &str) -> Option<crate::MimeType> {
-        match ext {
-            "jpg" | "jpeg" => Some(crate::MimeType::Image(Image::Jpeg)),
-            "png" => Some(crate::MimeType::Image(Image::Png)),
-            "gif" => Some(crate::MimeType::Image(Image::Gif)),
-            "webp" => Some(crate::MimeType::Image(Image::Webp)),
-            "cr2" => Some(crate::MimeType::Image(Image::Cr2)),
-            "tif" | "tiff" => Some(crate::MimeType::Image(Image::Tif)),
-            "bmp" => Some(crate::MimeType::Image(Image::Bmp)),
-            "heif" => Some(crate::MimeType::Image(Image::Heif)),
-            "avif" => Some(crate::MimeType::Image(Image::Avif)),
-            "jxr" => Some(crate::MimeType::Image(Image::Jxr)),
-            "psd" => Some(crate::MimeType::Image(Image::Psd)),
-            "ico" => Some(crate::MimeType::Image(Image::Ico)),
-            "ora" => Some(crate::MimeType::Image(Image::Ora)),
-            "djvu" => Some(crate::MimeType::Image(Image::Djvu)),
-            _ => None,
-        }
-    }
-
-    fn from_mime(mime: &str) -> Option<crate::MimeType> {
-        match mime {
-            "image/jpeg" => Some(crate::MimeType::Image(Image::Jpeg)),
-            "image/png" => Some(crate::MimeType::Image(Image::Png)),
-            "image/gif" => Some(crate::MimeType::Image(Image::Gif)),
-            "image/webp" => Some(crate::MimeType::Image(Image::Webp)),
-            "image/x-canon-cr2" => Some(crate::MimeType::Image(Image::Cr2)),
-            "image/tiff" => Some(crate::MimeType::Image(Image::Tif)),
-            "image/bmp" => Some(crate::MimeType::Image(Image::Bmp)),
-            "image/heif" => Some(crate::MimeType::Image(Image::Heif)),
-            "image/avif" => Some(crate::MimeType::Image(Image::Avif)),
-            "image/vnd.ms-photo" => Some(crate::MimeType::Image(Image::Jxr)),
-            "image/vnd.adobe.photoshop" => Some(crate::MimeType::Image(Image::Psd)),
-            "image/vnd.microsoft.icon" => Some(crate::MimeType::Image(Image::Ico)),
-            "image/openraster" => Some(crate::MimeType::Image(Image::Ora)),
-            "image/vnd.djvu" => Some(crate::MimeType::Image(Image::Djvu)),
-            _ => None,
-        }
+mime_category! {
+    /// Image file formats.
+    ///
+    /// Supports common image formats including raster and vector types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::Image;
+    ///
+    /// assert_eq!(Image::Jpeg.extensions(), &["jpg", "jpeg"]);
+    /// assert_eq!(Image::Jpeg.preferred_extension(), "jpg");
+    /// ```
+    pub enum Image {
+        /// JPEG image format
+        Jpeg {
+            mime: "image/jpeg",
+            aliases: ["image/pjpeg"],
+            extensions: ["jpg", "jpeg"],
+        },
+        /// PNG image format
+        Png {
+            mime: "image/png",
+            aliases: [],
+            extensions: ["png"],
+        },
+        /// GIF image format
+        Gif {
+            mime: "image/gif",
+            aliases: [],
+            extensions: ["gif"],
+        },
+        /// WebP image format
+        Webp {
+            mime: "image/webp",
+            aliases: [],
+            extensions: ["webp"],
+        },
+        /// Canon Raw 2 format
+        Cr2 {
+            mime: "image/x-canon-cr2",
+            aliases: [],
+            extensions: ["cr2"],
+        },
+        /// TIFF image format
+        Tif {
+            mime: "image/tiff",
+            aliases: ["image/tiff-fx"],
+            extensions: ["tif", "tiff"],
+        },
+        /// BMP image format
+        Bmp {
+            mime: "image/bmp",
+            aliases: ["image/x-bmp", "image/x-ms-bmp"],
+            extensions: ["bmp"],
+        },
+        /// HEIF image format
+        Heif {
+            mime: "image/heif",
+            aliases: [],
+            extensions: ["heif"],
+        },
+        /// AVIF image format
+        Avif {
+            mime: "image/avif",
+            aliases: [],
+            extensions: ["avif"],
+        },
+        /// JPEG XR format
+        Jxr {
+            mime: "image/vnd.ms-photo",
+            aliases: [],
+            extensions: ["jxr"],
+        },
+        /// Adobe Photoshop document
+        Psd {
+            mime: "image/vnd.adobe.photoshop",
+            aliases: [],
+            extensions: ["psd"],
+        },
+        /// ICO icon format
+        Ico {
+            mime: "image/vnd.microsoft.icon",
+            aliases: ["image/x-icon"],
+            extensions: ["ico"],
+        },
+        /// OpenRaster format
+        Ora {
+            mime: "image/openraster",
+            aliases: [],
+            extensions: ["ora"],
+        },
+        /// DjVu document format
+        Djvu {
+            mime: "image/vnd.djvu",
+            aliases: [],
+            extensions: ["djvu"],
+        },
+        /// Windows animated cursor format
+        Ani {
+            mime: "image/x-navi-animation",
+            aliases: ["application/x-navi-animation"],
+            extensions: ["ani"],
+        },
     }
 }
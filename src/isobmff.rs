@@ -0,0 +1,216 @@
+use crate::{Audio, Image, MimeType, Video};
+
+/// Top-level box types a file in the ISO base media format can start with.
+const TOP_LEVEL_BOXES: &[&[u8]] = &[
+    b"ftyp", b"moov", b"mdat", b"free", b"skip", b"wide", b"pnot",
+];
+
+/// The brands and track handlers of an ISO base media file (ISO/IEC 14496-12).
+///
+/// MP4, M4A, M4V, QuickTime, 3GP, HEIF and AVIF files share this container and
+/// are told apart by the brands in their `ftyp` box. When the brands are
+/// generic (e.g. `isom`), the handler types of the `moov` tracks and the `meta`
+/// box decide between audio, video and still images.
+///
+/// Only the given bytes are parsed, so boxes beyond them, such as a `moov` box
+/// stored after the media data, are not seen.
+///
+/// # Examples
+///
+/// ```
+/// use mime_type::{Image, IsoBmff, MimeType};
+///
+/// let ftyp = b"\0\0\0\x1cftypmif1\0\0\0\0mif1miafavif";
+/// let bmff = IsoBmff::parse(ftyp).unwrap();
+/// assert_eq!(bmff.major_brand(), Some("mif1"));
+/// assert_eq!(bmff.compatible_brands(), &["mif1", "miaf", "avif"]);
+/// assert_eq!(bmff.mime_type(), MimeType::Image(Image::Avif));
+///
+/// assert!(IsoBmff::parse(b"\x89PNG\r\n\x1a\n").is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IsoBmff {
+    major_brand: Option<String>,
+    minor_version: u32,
+    compatible_brands: Vec<String>,
+    handlers: Vec<String>,
+}
+
+impl IsoBmff {
+    /// Parses the leading boxes of a file.
+    ///
+    /// Returns `None` if the bytes do not start with a top-level box.
+    pub fn parse(bytes: &[u8]) -> Option<IsoBmff> {
+        let first = boxes(bytes).next()?;
+        if !TOP_LEVEL_BOXES.contains(&first.0) {
+            return None;
+        }
+
+        let mut bmff = IsoBmff {
+            major_brand: None,
+            minor_version: 0,
+            compatible_brands: Vec::new(),
+            handlers: Vec::new(),
+        };
+        for (kind, payload) in boxes(bytes) {
+            match kind {
+                b"ftyp" if bmff.major_brand.is_none() => {
+                    let Some(major) = payload.get(..4) else {
+                        continue;
+                    };
+                    bmff.major_brand = Some(fourcc(major));
+                    bmff.minor_version = payload
+                        .get(4..8)
+                        .map_or(0, |b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+                    bmff.compatible_brands = payload
+                        .get(8..)
+                        .unwrap_or_default()
+                        .chunks_exact(4)
+                        .map(fourcc)
+                        .collect();
+                }
+                b"moov" => {
+                    let hdlrs = children(payload, b"trak")
+                        .flat_map(|trak| children(trak, b"mdia"))
+                        .flat_map(|mdia| children(mdia, b"hdlr"));
+                    for hdlr in hdlrs {
+                        bmff.add_handler(hdlr);
+                    }
+                }
+                // `meta` is a full box: its children follow the version and flags
+                b"meta" => {
+                    for hdlr in children(payload.get(4..).unwrap_or_default(), b"hdlr") {
+                        bmff.add_handler(hdlr);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Some(bmff)
+    }
+
+    /// Returns the major brand of the `ftyp` box, or `None` for files without
+    /// one, such as early QuickTime movies.
+    pub fn major_brand(&self) -> Option<&str> {
+        self.major_brand.as_deref()
+    }
+
+    /// Returns the minor version of the major brand.
+    pub fn minor_version(&self) -> u32 {
+        self.minor_version
+    }
+
+    /// Returns the compatible brands of the `ftyp` box, in file order.
+    pub fn compatible_brands(&self) -> &[String] {
+        &self.compatible_brands
+    }
+
+    /// Returns the handler types found in the `moov` tracks and the `meta`
+    /// box, e.g. `vide`, `soun` or `pict`, without duplicates.
+    pub fn handlers(&self) -> &[String] {
+        &self.handlers
+    }
+
+    /// Maps the brands and handlers to a MIME type.
+    ///
+    /// A specific major brand wins, then the first specific compatible brand.
+    /// Otherwise, files without video tracks are M4A audio, and files with
+    /// only a still image are HEIF. Everything else is MP4.
+    pub fn mime_type(&self) -> MimeType {
+        let Some(major) = &self.major_brand else {
+            return MimeType::Video(Video::Mov);
+        };
+        if let Some(mime) = std::iter::once(major)
+            .chain(&self.compatible_brands)
+            .find_map(|brand| brand_type(brand))
+        {
+            return mime;
+        }
+
+        let has = |handler: &str| self.handlers.iter().any(|h| h == handler);
+        if has("vide") {
+            MimeType::Video(Video::Mp4)
+        } else if has("soun") {
+            MimeType::Audio(Audio::M4a)
+        } else if has("pict") || matches!(major.as_str(), "mif1" | "msf1") {
+            MimeType::Image(Image::Heif)
+        } else {
+            MimeType::Video(Video::Mp4)
+        }
+    }
+
+    fn add_handler(&mut self, hdlr: &[u8]) {
+        // version and flags, pre_defined, then the handler type
+        let Some(handler) = hdlr.get(8..12) else {
+            return;
+        };
+        let handler = fourcc(handler);
+        if !self.handlers.contains(&handler) {
+            self.handlers.push(handler);
+        }
+    }
+}
+
+/// Returns the type a brand specifically identifies.
+fn brand_type(brand: &str) -> Option<MimeType> {
+    let mime = match brand {
+        "avif" | "avis" => MimeType::Image(Image::Avif),
+        "heic" | "heix" | "hevc" | "hevx" | "heim" | "heis" => MimeType::Image(Image::Heif),
+        "qt  " => MimeType::Video(Video::Mov),
+        "M4A " | "M4B " | "M4P " => MimeType::Audio(Audio::M4a),
+        "M4V " | "M4VH" | "M4VP" => MimeType::Video(Video::M4v),
+        _ if ["3gp", "3ge", "3gg", "3gr", "3gs"]
+            .iter()
+            .any(|prefix| brand.starts_with(prefix)) =>
+        {
+            MimeType::Video(Video::ThreeGp)
+        }
+        _ => return None,
+    };
+    Some(mime)
+}
+
+fn fourcc(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+/// Iterates the boxes in `bytes` as `(type, payload)` pairs.
+///
+/// A box extending past the end of `bytes` is cut short, so the leading part
+/// of a file can be parsed.
+fn boxes(bytes: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let header = bytes.get(pos..pos + 8)?;
+        let kind = &header[4..8];
+        let (size, header_len) =
+            match u32::from_be_bytes([header[0], header[1], header[2], header[3]]) {
+                0 => ((bytes.len() - pos) as u64, 8),
+                1 => {
+                    let b = bytes.get(pos + 8..pos + 16)?;
+                    (
+                        u64::from_be_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]),
+                        16,
+                    )
+                }
+                size => (u64::from(size), 8),
+            };
+        if size < header_len as u64 {
+            return None;
+        }
+        let end = usize::try_from(size)
+            .ok()
+            .and_then(|size| pos.checked_add(size))
+            .map_or(bytes.len(), |end| end.min(bytes.len()));
+        let payload = bytes.get(pos + header_len..end)?;
+        pos = end;
+        Some((kind, payload))
+    })
+}
+
+/// Iterates the payloads of the child boxes of a given type.
+fn children<'a>(payload: &'a [u8], kind: &'static [u8]) -> impl Iterator<Item = &'a [u8]> {
+    boxes(payload)
+        .filter(move |(child, _)| *child == kind)
+        .map(|(_, payload)| payload)
+}
@@ -14,29 +14,78 @@
 //!
 //! // Get MIME type from MIME string
 //! let mime = MimeType::from_mime("video/mp4").unwrap();
+//!
+//! // Detect MIME type from file contents
+//! let mime = MimeType::from_bytes(b"\x89PNG\r\n\x1a\n").unwrap();
+//! assert_eq!(mime.to_string(), "image/png");
 //! ```
 
+#[macro_use]
+mod macros;
+
+mod accept;
 mod application;
 mod archive;
 mod audio;
 mod book;
+mod category;
+mod detector;
 mod document;
+mod ebml;
 mod font;
 mod image;
+mod isobmff;
+mod libmagic;
+mod media_range;
+mod media_type;
 mod mime_format;
+mod mime_types;
+mod ogg;
+mod regex;
+mod registry;
+mod riff;
+mod server_config;
+mod shared_mime_info;
+mod signature;
+mod sniff;
+mod text;
+mod tika;
 mod video;
+mod xml;
+mod zip;
 
 use std::fmt::{self, Display, Formatter};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
 
+pub use accept::{Accept, AcceptItem};
 pub use application::Application;
 pub use archive::Archive;
 pub use audio::Audio;
 pub use book::Book;
+pub use category::{Category, ParseCategoryError};
+pub use detector::{Detection, Detector};
 pub use document::Document;
+pub use ebml::Ebml;
 pub use font::Font;
 pub use image::Image;
+pub use isobmff::IsoBmff;
+pub use libmagic::ParseLibmagicError;
+pub use media_range::MediaRange;
+pub use media_type::{MediaType, ParseMediaTypeError};
 pub use mime_format::MimeFormat;
+pub use mime_types::ParseMimeTypesError;
+pub use ogg::{Ogg, OggCodec};
+pub use registry::MimeRegistry;
+pub use riff::{Riff, WebpFormat};
+pub use server_config::ServerConfig;
+pub use shared_mime_info::ParseSharedMimeInfoError;
+pub use sniff::{MimeSniffer, SniffContext};
+pub use text::Text;
+pub use tika::ParseTikaError;
 pub use video::Video;
+pub use zip::Zip;
 
 /// Main MIME type enum containing all supported categories.
 ///
@@ -60,6 +109,24 @@ pub enum MimeType {
     Font(Font),
     /// Application and executable formats (EXE, WASM, ELF, etc.)
     Application(Application),
+    /// Text formats (HTML, CSS, CSV, etc.)
+    Text(Text),
+    /// A syntactically valid media type that is not one of the known formats.
+    ///
+    /// Keeps the parsed type, subtype and parameters so that unknown types
+    /// such as `application/vnd.foo+json` can be passed through unchanged.
+    ///
+    /// ```
+    /// use mime_type::{MimeFormat, MimeType};
+    ///
+    /// let mime = MimeType::from_mime("application/vnd.foo+json; v=2").unwrap();
+    /// match &mime {
+    ///     MimeType::Other(media) => assert_eq!(media.suffix(), Some("json")),
+    ///     _ => unreachable!(),
+    /// }
+    /// assert_eq!(mime.to_string(), "application/vnd.foo+json; v=2");
+    /// ```
+    Other(MediaType),
 }
 
 impl Display for MimeType {
@@ -73,8 +140,540 @@ impl Display for MimeType {
             MimeType::Document(doc) => write!(f, "{}", doc),
             MimeType::Font(font) => write!(f, "{}", font),
             MimeType::Application(app) => write!(f, "{}", app),
+            MimeType::Text(text) => write!(f, "{}", text),
+            MimeType::Other(media) => write!(f, "{}", media),
+        }
+    }
+}
+
+impl MimeType {
+    /// Detects a MIME type from the contents of a file.
+    ///
+    /// Matches the leading bytes against known magic-number signatures, so
+    /// renamed or extensionless files can still be classified. Some formats
+    /// (e.g. MOBI, TAR) are identified at larger offsets, so pass at least the
+    /// first few kilobytes of the file when available. Only the first 8 KiB
+    /// are examined; [`MimeType::from_file`] also reads the central directory
+    /// at the end of larger ZIP files.
+    ///
+    /// Returns `None` if no signature matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{Archive, Image, MimeType};
+    ///
+    /// let mime = MimeType::from_bytes(b"GIF89a\x01\x00\x01\x00");
+    /// assert_eq!(mime, Some(MimeType::Image(Image::Gif)));
+    ///
+    /// // A BMP file header followed by a 40-byte BITMAPINFOHEADER
+    /// let mime = MimeType::from_bytes(b"BM:\0\0\0\0\0\0\0\x36\0\0\0\x28\0\0\0");
+    /// assert_eq!(mime, Some(MimeType::Image(Image::Bmp)));
+    ///
+    /// assert!(MimeType::from_bytes(b"BMW motorcycles").is_none());
+    /// assert!(MimeType::from_bytes(b"plain text").is_none());
+    ///
+    /// // An OLE stream name past the first 8 KiB is not seen
+    /// let mut ole = b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1".to_vec();
+    /// ole.resize(9000, 0);
+    /// ole.extend("WordDocument".encode_utf16().flat_map(u16::to_le_bytes));
+    /// assert_eq!(MimeType::from_bytes(&ole), Some(MimeType::Archive(Archive::Msi)));
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Option<MimeType> {
+        signature::detect(bytes)
+    }
+
+    /// Iterates over every supported MIME type, grouped by category.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{Image, MimeType};
+    ///
+    /// let images = MimeType::iter().filter(MimeType::is_image).count();
+    /// assert_eq!(images, Image::ALL.len());
+    /// ```
+    ///
+    /// Every format round-trips through the lookup tables:
+    ///
+    /// ```
+    /// use mime_type::MimeType;
+    ///
+    /// for mime in MimeType::iter() {
+    ///     assert!(MimeType::from_mime_all(&mime.to_string()).contains(&mime));
+    ///     for alias in mime.aliases() {
+    ///         assert!(MimeType::from_mime_all(alias).contains(&mime));
+    ///     }
+    ///     assert!(!mime.extensions().is_empty());
+    ///     for ext in mime.extensions() {
+    ///         assert!(MimeType::from_ext_all(ext).contains(&mime));
+    ///     }
+    /// }
+    /// ```
+    pub fn iter() -> impl Iterator<Item = MimeType> {
+        Image::ALL
+            .iter()
+            .cloned()
+            .map(MimeType::Image)
+            .chain(Video::ALL.iter().cloned().map(MimeType::Video))
+            .chain(Audio::ALL.iter().cloned().map(MimeType::Audio))
+            .chain(Archive::ALL.iter().cloned().map(MimeType::Archive))
+            .chain(Book::ALL.iter().cloned().map(MimeType::Book))
+            .chain(Document::ALL.iter().cloned().map(MimeType::Document))
+            .chain(Font::ALL.iter().cloned().map(MimeType::Font))
+            .chain(Application::ALL.iter().cloned().map(MimeType::Application))
+            .chain(Text::ALL.iter().cloned().map(MimeType::Text))
+    }
+
+    /// Iterates over the `(extension, MimeType)` pairs used by `from_ext`.
+    ///
+    /// Pairs come in lookup order, so for an extension listed more than once
+    /// (e.g. `epub`) the first pair is the one `from_ext` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{MimeFormat, MimeType};
+    ///
+    /// for (ext, _) in MimeType::iter_extensions() {
+    ///     assert!(MimeType::from_ext(ext).is_some());
+    /// }
+    /// ```
+    pub fn iter_extensions() -> impl Iterator<Item = (&'static str, MimeType)> {
+        image::EXTENSIONS
+            .iter()
+            .map(|(ext, f)| (*ext, MimeType::Image(f.clone())))
+            .chain(
+                video::EXTENSIONS
+                    .iter()
+                    .map(|(ext, f)| (*ext, MimeType::Video(f.clone()))),
+            )
+            .chain(
+                audio::EXTENSIONS
+                    .iter()
+                    .map(|(ext, f)| (*ext, MimeType::Audio(f.clone()))),
+            )
+            .chain(
+                archive::EXTENSIONS
+                    .iter()
+                    .map(|(ext, f)| (*ext, MimeType::Archive(f.clone()))),
+            )
+            .chain(
+                book::EXTENSIONS
+                    .iter()
+                    .map(|(ext, f)| (*ext, MimeType::Book(f.clone()))),
+            )
+            .chain(
+                document::EXTENSIONS
+                    .iter()
+                    .map(|(ext, f)| (*ext, MimeType::Document(f.clone()))),
+            )
+            .chain(
+                font::EXTENSIONS
+                    .iter()
+                    .map(|(ext, f)| (*ext, MimeType::Font(f.clone()))),
+            )
+            .chain(
+                application::EXTENSIONS
+                    .iter()
+                    .map(|(ext, f)| (*ext, MimeType::Application(f.clone()))),
+            )
+            .chain(
+                text::EXTENSIONS
+                    .iter()
+                    .map(|(ext, f)| (*ext, MimeType::Text(f.clone()))),
+            )
+    }
+
+    /// Iterates over the `(MIME string, MimeType)` pairs used by `from_mime`.
+    ///
+    /// Pairs come in lookup order, so for a MIME string listed more than once
+    /// the first pair is the one `from_mime` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{MimeFormat, MimeType};
+    ///
+    /// for (mime, _) in MimeType::iter_mimes() {
+    ///     assert!(MimeType::from_mime(mime).is_some());
+    /// }
+    /// ```
+    pub fn iter_mimes() -> impl Iterator<Item = (&'static str, MimeType)> {
+        image::MIME_TYPES
+            .iter()
+            .map(|(mime, f)| (*mime, MimeType::Image(f.clone())))
+            .chain(
+                video::MIME_TYPES
+                    .iter()
+                    .map(|(mime, f)| (*mime, MimeType::Video(f.clone()))),
+            )
+            .chain(
+                audio::MIME_TYPES
+                    .iter()
+                    .map(|(mime, f)| (*mime, MimeType::Audio(f.clone()))),
+            )
+            .chain(
+                archive::MIME_TYPES
+                    .iter()
+                    .map(|(mime, f)| (*mime, MimeType::Archive(f.clone()))),
+            )
+            .chain(
+                book::MIME_TYPES
+                    .iter()
+                    .map(|(mime, f)| (*mime, MimeType::Book(f.clone()))),
+            )
+            .chain(
+                document::MIME_TYPES
+                    .iter()
+                    .map(|(mime, f)| (*mime, MimeType::Document(f.clone()))),
+            )
+            .chain(
+                font::MIME_TYPES
+                    .iter()
+                    .map(|(mime, f)| (*mime, MimeType::Font(f.clone()))),
+            )
+            .chain(
+                application::MIME_TYPES
+                    .iter()
+                    .map(|(mime, f)| (*mime, MimeType::Application(f.clone()))),
+            )
+            .chain(
+                text::MIME_TYPES
+                    .iter()
+                    .map(|(mime, f)| (*mime, MimeType::Text(f.clone()))),
+            )
+    }
+
+    /// Returns every MIME type for a file extension, in priority order.
+    ///
+    /// The first candidate is the one [`MimeFormat::from_ext`] returns.
+    /// Returns an empty vector if the extension is not recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{Archive, Book, MimeType};
+    ///
+    /// let candidates = MimeType::from_ext_all("epub");
+    /// assert_eq!(
+    ///     candidates,
+    ///     vec![MimeType::Archive(Archive::Epub), MimeType::Book(Book::Epub)]
+    /// );
+    /// ```
+    pub fn from_ext_all(ext: &str) -> Vec<MimeType> {
+        MimeType::iter_extensions()
+            .filter(|(key, _)| *key == ext)
+            .map(|(_, mime)| mime)
+            .collect()
+    }
+
+    /// Returns every MIME type for a MIME string, in priority order.
+    ///
+    /// The first candidate is the one [`MimeFormat::from_mime`] returns: a
+    /// valid but unknown MIME string yields a single [`MimeType::Other`], and
+    /// an invalid one yields an empty vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{Application, MimeType};
+    ///
+    /// let candidates = MimeType::from_mime_all("application/x-executable");
+    /// assert_eq!(
+    ///     candidates,
+    ///     vec![
+    ///         MimeType::Application(Application::Elf),
+    ///         MimeType::Application(Application::Obj),
+    ///     ]
+    /// );
+    /// ```
+    pub fn from_mime_all(mime: &str) -> Vec<MimeType> {
+        let candidates = known_mime_candidates(mime);
+        if !candidates.is_empty() {
+            return candidates;
+        }
+        let Ok(media) = MediaType::parse(mime) else {
+            return Vec::new();
+        };
+        let candidates = known_mime_candidates(&media.essence());
+        if candidates.is_empty() {
+            vec![MimeType::Other(media)]
+        } else {
+            candidates
         }
     }
+
+    /// Converts a file extension to a MIME type, preferring a category.
+    ///
+    /// Returns the first candidate in `preferred` if there is one, and falls
+    /// back to the regular [`MimeFormat::from_ext`] result otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{Book, Category, MimeType};
+    ///
+    /// let mime = MimeType::from_ext_prefer("epub", Category::Book);
+    /// assert_eq!(mime, Some(MimeType::Book(Book::Epub)));
+    /// ```
+    pub fn from_ext_prefer(ext: &str, preferred: Category) -> Option<MimeType> {
+        prefer(MimeType::from_ext_all(ext), preferred)
+    }
+
+    /// Converts a MIME string to a MIME type, preferring a category.
+    ///
+    /// Returns the first candidate in `preferred` if there is one, and falls
+    /// back to the regular [`MimeFormat::from_mime`] result otherwise.
+    pub fn from_mime_prefer(mime: &str, preferred: Category) -> Option<MimeType> {
+        prefer(MimeType::from_mime_all(mime), preferred)
+    }
+
+    /// Returns the category of this MIME type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{Audio, Category, MimeType};
+    ///
+    /// let mime = MimeType::Audio(Audio::Flac);
+    /// assert_eq!(mime.category(), Category::Audio);
+    /// assert!(mime.is_audio());
+    /// assert!(!mime.is_video());
+    /// ```
+    pub fn category(&self) -> Category {
+        match self {
+            MimeType::Image(_) => Category::Image,
+            MimeType::Video(_) => Category::Video,
+            MimeType::Audio(_) => Category::Audio,
+            MimeType::Archive(_) => Category::Archive,
+            MimeType::Book(_) => Category::Book,
+            MimeType::Document(_) => Category::Document,
+            MimeType::Font(_) => Category::Font,
+            MimeType::Application(_) => Category::Application,
+            MimeType::Text(_) => Category::Text,
+            MimeType::Other(_) => Category::Other,
+        }
+    }
+
+    /// Returns `true` if this is an image format.
+    pub fn is_image(&self) -> bool {
+        self.category() == Category::Image
+    }
+
+    /// Returns `true` if this is a video format.
+    pub fn is_video(&self) -> bool {
+        self.category() == Category::Video
+    }
+
+    /// Returns `true` if this is audio format.
+    pub fn is_audio(&self) -> bool {
+        self.category() == Category::Audio
+    }
+
+    /// Returns `true` if this is an archive or compressed format.
+    pub fn is_archive(&self) -> bool {
+        self.category() == Category::Archive
+    }
+
+    /// Returns `true` if this is an e-book format.
+    pub fn is_book(&self) -> bool {
+        self.category() == Category::Book
+    }
+
+    /// Returns `true` if this is a document format.
+    pub fn is_document(&self) -> bool {
+        self.category() == Category::Document
+    }
+
+    /// Returns `true` if this is a font format.
+    pub fn is_font(&self) -> bool {
+        self.category() == Category::Font
+    }
+
+    /// Returns `true` if this is an application or executable format.
+    pub fn is_application(&self) -> bool {
+        self.category() == Category::Application
+    }
+
+    /// Returns `true` if this is a text format.
+    pub fn is_text(&self) -> bool {
+        self.category() == Category::Text
+    }
+
+    /// Returns all file extensions for this MIME type, preferred extension first.
+    ///
+    /// [`MimeType::Other`] has no known extensions and returns an empty slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{Image, MimeType};
+    ///
+    /// let mime = MimeType::Image(Image::Tif);
+    /// assert_eq!(mime.extensions(), &["tif", "tiff"]);
+    /// ```
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            MimeType::Image(img) => img.extensions(),
+            MimeType::Video(vid) => vid.extensions(),
+            MimeType::Audio(aud) => aud.extensions(),
+            MimeType::Archive(arch) => arch.extensions(),
+            MimeType::Book(book) => book.extensions(),
+            MimeType::Document(doc) => doc.extensions(),
+            MimeType::Font(font) => font.extensions(),
+            MimeType::Application(app) => app.extensions(),
+            MimeType::Text(text) => text.extensions(),
+            MimeType::Other(_) => &[],
+        }
+    }
+
+    /// Returns the alternative MIME strings accepted for this MIME type
+    /// besides its canonical `Display` string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{Image, MimeType, MimeFormat};
+    ///
+    /// let ico = MimeType::Image(Image::Ico);
+    /// assert_eq!(ico.aliases(), &["image/x-icon"]);
+    /// assert_eq!(MimeType::from_mime("image/x-icon"), Some(ico));
+    /// ```
+    pub fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            MimeType::Image(img) => img.aliases(),
+            MimeType::Video(vid) => vid.aliases(),
+            MimeType::Audio(aud) => aud.aliases(),
+            MimeType::Archive(arch) => arch.aliases(),
+            MimeType::Book(book) => book.aliases(),
+            MimeType::Document(doc) => doc.aliases(),
+            MimeType::Font(font) => font.aliases(),
+            MimeType::Application(app) => app.aliases(),
+            MimeType::Text(text) => text.aliases(),
+            MimeType::Other(_) => &[],
+        }
+    }
+
+    /// Returns the preferred file extension for this MIME type.
+    ///
+    /// Useful for naming downloaded or generated files. Returns `None` for
+    /// [`MimeType::Other`], which has no known extensions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{Image, MimeType};
+    ///
+    /// let mime = MimeType::Image(Image::Jpeg);
+    /// assert_eq!(mime.preferred_extension(), Some("jpg"));
+    /// ```
+    pub fn preferred_extension(&self) -> Option<&'static str> {
+        self.extensions().first().copied()
+    }
+
+    /// Determines the MIME type from the extension of a path.
+    ///
+    /// The extension is matched as-is first and then lowercased, so `.JPG`
+    /// resolves like `.jpg` while case-sensitive extensions such as `.Z` keep
+    /// working. Dotfiles without a further extension (e.g. `.bashrc`) and
+    /// non-UTF-8 extensions return `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use mime_type::{Image, MimeType};
+    ///
+    /// let mime = MimeType::from_path(Path::new("photos/IMG_0001.JPG"));
+    /// assert_eq!(mime, Some(MimeType::Image(Image::Jpeg)));
+    ///
+    /// assert!(MimeType::from_path(Path::new(".png")).is_none());
+    /// ```
+    pub fn from_path(path: &Path) -> Option<MimeType> {
+        let ext = path.extension()?.to_str()?;
+        MimeType::from_ext(ext).or_else(|| MimeType::from_ext(&ext.to_ascii_lowercase()))
+    }
+
+    /// Determines the MIME type of a file from its contents and extension.
+    ///
+    /// Reads the file header and detects the type with [`MimeType::from_bytes`].
+    /// For ZIP files, the central directory at the end of the file is read as
+    /// well to identify the format stored in them. The extension is used when
+    /// the contents are not recognized, or when it names a more specific
+    /// format stored in the detected container (e.g. a `.docx` whose contents
+    /// only identify as ZIP).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use mime_type::MimeType;
+    ///
+    /// let mime = MimeType::from_file(Path::new("upload.bin"))?;
+    /// println!("{:?}", mime);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn from_file(path: &Path) -> io::Result<Option<MimeType>> {
+        let mut file = File::open(path)?;
+        let mut header = Vec::with_capacity(signature::HEADER_LEN);
+        file.by_ref()
+            .take(signature::HEADER_LEN as u64)
+            .read_to_end(&mut header)?;
+
+        let mut by_content = MimeType::from_bytes(&header);
+        if by_content == Some(MimeType::Archive(Archive::Zip)) {
+            by_content = zip::detect_file(&mut file, &header)?.or(by_content);
+        }
+        let by_ext = MimeType::from_path(path);
+        Ok(match (by_content, by_ext) {
+            (Some(content), Some(ext)) if signature::is_refinement(&content, &ext) => Some(ext),
+            (Some(content), _) => Some(content),
+            (None, ext) => ext,
+        })
+    }
+}
+
+impl From<MediaType> for MimeType {
+    /// Resolves a media type to a known format, or wraps it in
+    /// [`MimeType::Other`]. Parameters are dropped for known formats.
+    fn from(media: MediaType) -> Self {
+        known_from_mime(&media.essence()).unwrap_or(MimeType::Other(media))
+    }
+}
+
+/// Looks up a MIME string in the known format tables only.
+pub(crate) fn known_from_mime(mime: &str) -> Option<MimeType> {
+    Image::from_mime(mime)
+        .or_else(|| Video::from_mime(mime))
+        .or_else(|| Audio::from_mime(mime))
+        .or_else(|| Archive::from_mime(mime))
+        .or_else(|| Book::from_mime(mime))
+        .or_else(|| Document::from_mime(mime))
+        .or_else(|| Font::from_mime(mime))
+        .or_else(|| Application::from_mime(mime))
+        .or_else(|| Text::from_mime(mime))
+}
+
+/// Returns every known format for a MIME string, in lookup order.
+fn known_mime_candidates(mime: &str) -> Vec<MimeType> {
+    MimeType::iter_mimes()
+        .filter(|(key, _)| *key == mime)
+        .map(|(_, mime)| mime)
+        .collect()
+}
+
+/// Picks the first candidate in the preferred category, or the first overall.
+fn prefer(candidates: Vec<MimeType>, preferred: Category) -> Option<MimeType> {
+    let idx = candidates
+        .iter()
+        .position(|mime| mime.category() == preferred)
+        .unwrap_or(0);
+    candidates.into_iter().nth(idx)
 }
 
 impl MimeFormat for MimeType {
@@ -87,16 +686,15 @@ impl MimeFormat for MimeType {
             .or_else(|| Document::from_ext(ext))
             .or_else(|| Font::from_ext(ext))
             .or_else(|| Application::from_ext(ext))
+            .or_else(|| Text::from_ext(ext))
     }
 
+    /// Converts a MIME type string to a MIME type.
+    ///
+    /// Known MIME strings resolve to their format, also when they differ in
+    /// case or carry parameters. Any other syntactically valid media type
+    /// resolves to [`MimeType::Other`]; only malformed strings return `None`.
     fn from_mime(mime: &str) -> Option<MimeType> {
-        Image::from_mime(mime)
-            .or_else(|| Video::from_mime(mime))
-            .or_else(|| Audio::from_mime(mime))
-            .or_else(|| Archive::from_mime(mime))
-            .or_else(|| Book::from_mime(mime))
-            .or_else(|| Document::from_mime(mime))
-            .or_else(|| Font::from_mime(mime))
-            .or_else(|| Application::from_mime(mime))
+        known_from_mime(mime).or_else(|| MediaType::parse(mime).ok().map(MimeType::from))
     }
 }
@@ -0,0 +1,807 @@
+use crate::regex::Regex;
+use crate::registry::{BUILTIN_PRIORITY, Sniffer};
+use crate::{MimeRegistry, MimeType};
+use std::cmp::Reverse;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
+
+/// Default number of bytes searched by `search` and `regex` tests, and the
+/// most a `regex` test examines.
+const DEFAULT_SEARCH_LEN: usize = 8192;
+
+/// Bytes allowed per line when a `regex` test is limited to a number of
+/// lines, as in file(1).
+const REGEX_LINE_LEN: usize = 80;
+
+/// Base unit of libmagic's rule strength.
+const STRENGTH_UNIT: i64 = 10;
+
+impl MimeRegistry {
+    /// Loads rules written in libmagic's `magic(5)` syntax.
+    ///
+    /// Rules take part in [`MimeRegistry::from_bytes`] ahead of the built-in
+    /// signatures, ordered by libmagic's strength, and report the type from
+    /// the first `!:mime` annotation on a matching line. Rules that match
+    /// without one are passed over.
+    ///
+    /// The supported subset covers continuation levels, absolute, relative
+    /// (`&`) and indirect (`(x.l+y)`) offsets, the `byte`, `short`, `long`
+    /// and `quad` numeric types in native, `le` and `be` byte order with
+    /// masks, and the `string`, `search` and `regex` types with the `/c`
+    /// flag. Lines using other types or syntax, such as `date`, `name` or
+    /// `use`, never match, and neither do their continuations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a line is missing its offset, type or test, if a
+    /// continuation has no parent, or if a `!:mime` annotation is not a valid
+    /// media type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::MimeRegistry;
+    ///
+    /// let mut registry = MimeRegistry::new();
+    /// registry
+    ///     .load_libmagic(
+    ///         "0\tstring\tSCENE\tScene file\n\
+    ///          >8\tbyte\t2\tversion 2\n\
+    ///          !:mime\tapplication/x-scene\n",
+    ///     )
+    ///     .unwrap();
+    /// let mime = registry.from_bytes(b"SCENE\0\0\0\x02").unwrap();
+    /// assert_eq!(mime.to_string(), "application/x-scene");
+    /// assert!(registry.from_bytes(b"SCENE\0\0\0\x01").is_none());
+    /// ```
+    ///
+    /// Offsets can be read from the data or follow the parent's match, and
+    /// the type comes from the first `!:mime` on the matching path:
+    ///
+    /// ```
+    /// use mime_type::MimeRegistry;
+    ///
+    /// let mut registry = MimeRegistry::empty();
+    /// registry
+    ///     .load_libmagic(
+    ///         "0\tstring\tIDX\n\
+    ///          >(4.l+2)\tstring\tBODY\n\
+    ///          !:mime\tapplication/x-indirect\n\
+    ///          0\tstring\tREL\n\
+    ///          >&1\tstring\tTAIL\n\
+    ///          !:mime\tapplication/x-relative\n\
+    ///          0\tsearch/16\tMARK\n\
+    ///          !:mime\tapplication/x-search\n\
+    ///          0\tregex/1l\t^@import[[:blank:]]+lib\n\
+    ///          !:mime\tapplication/x-regex\n\
+    ///          0\tbelong&0xFFFF0000\t0xCAFE0000\n\
+    ///          !:mime\tapplication/x-masked\n\
+    ///          0\tstring\tLVL\n\
+    ///          >3\tbyte\t1\n\
+    ///          !:mime\tapplication/x-level-one\n\
+    ///          >3\tbyte\t2\n\
+    ///          >>4\tbyte\t9\n\
+    ///          !:mime\tapplication/x-level-two\n",
+    ///     )
+    ///     .unwrap();
+    /// let detect = |bytes: &[u8]| registry.from_bytes(bytes).map(|mime| mime.to_string());
+    ///
+    /// // The long at offset 4 is 8, plus 2
+    /// assert_eq!(detect(b"IDX\0\x08\0\0\0..BODY").as_deref(), Some("application/x-indirect"));
+    /// // One byte after the end of `REL`
+    /// assert_eq!(detect(b"REL-TAIL").as_deref(), Some("application/x-relative"));
+    /// assert_eq!(detect(b"....MARK").as_deref(), Some("application/x-search"));
+    /// assert_eq!(detect(b"0123456789abcdefghMARK"), None);
+    /// assert_eq!(detect(b"@import  lib\n").as_deref(), Some("application/x-regex"));
+    /// assert_eq!(detect(b"\n@import lib\n"), None);
+    /// assert_eq!(detect(b"\xCA\xFE\x12\x34").as_deref(), Some("application/x-masked"));
+    /// assert_eq!(detect(b"LVL\x01").as_deref(), Some("application/x-level-one"));
+    /// assert_eq!(detect(b"LVL\x02\x09").as_deref(), Some("application/x-level-two"));
+    /// assert_eq!(detect(b"LVL\x02\x08"), None);
+    /// ```
+    ///
+    /// Out-of-range sizes and offsets in a rule never match:
+    ///
+    /// ```
+    /// use mime_type::MimeRegistry;
+    ///
+    /// let mut registry = MimeRegistry::empty();
+    /// registry
+    ///     .load_libmagic(
+    ///         "0\tsearch/18446744073709551615\tx\n\
+    ///          !:mime\tapplication/x-search\n\
+    ///          0\tbyte\tx\n\
+    ///          >(&9223372036854775807.l)\tbyte\tx\n\
+    ///          !:mime\tapplication/x-far\n",
+    ///     )
+    ///     .unwrap();
+    /// let mime = registry.from_bytes(b"box").unwrap();
+    /// assert_eq!(mime.to_string(), "application/x-search");
+    /// assert!(registry.from_bytes(b"abc").is_none());
+    ///
+    /// // Neither do regexes that expand too far or nest too deeply
+    /// let deep = format!("{}x{}", "(".repeat(100_000), ")".repeat(100_000));
+    /// let mut registry = MimeRegistry::empty();
+    /// registry
+    ///     .load_libmagic(&format!(
+    ///         "0\tregex\t((){{60000}}){{60000}}x\n!:mime\tapplication/x-empty\n\
+    ///          0\tregex\t{deep}\n!:mime\tapplication/x-deep\n"
+    ///     ))
+    ///     .unwrap();
+    /// assert!(registry.from_bytes(b"x").is_none());
+    /// ```
+    pub fn load_libmagic(&mut self, contents: &str) -> Result<(), ParseLibmagicError> {
+        let mut roots: Vec<Line> = Vec::new();
+        for (idx, raw) in contents.lines().enumerate() {
+            let err = |reason| ParseLibmagicError {
+                line: idx + 1,
+                reason,
+            };
+            let raw = raw.trim_end();
+            if raw.trim_start().is_empty() || raw.starts_with('#') {
+                continue;
+            }
+
+            if let Some(annotation) = raw.strip_prefix("!:") {
+                let (key, value) = annotation
+                    .split_once(char::is_whitespace)
+                    .unwrap_or((annotation, ""));
+                let last = roots
+                    .last_mut()
+                    .map(Line::last_descendant)
+                    .ok_or_else(|| err("annotation without a rule"))?;
+                match key {
+                    "mime" => {
+                        let mime = self
+                            .register(value.trim(), &[])
+                            .map_err(|_| err("invalid media type"))?;
+                        last.mime = Some(mime);
+                    }
+                    "strength" => last.strength_adjust = parse_strength(value.trim()),
+                    _ => {}
+                }
+                continue;
+            }
+
+            let level = raw.bytes().take_while(|&b| b == b'>').count();
+            let mut fields = Fields {
+                rest: &raw[level..],
+            };
+            let (Some(offset), Some(kind), Some(test)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(err("expected offset, type and test"));
+            };
+            let line = Line {
+                offset: parse_offset(offset),
+                test: parse_test(kind, test),
+                mime: None,
+                strength_adjust: None,
+                children: Vec::new(),
+            };
+
+            if level == 0 {
+                roots.push(line);
+                continue;
+            }
+            let mut parent = roots
+                .last_mut()
+                .ok_or_else(|| err("continuation without a parent"))?;
+            for _ in 1..level {
+                parent = parent
+                    .children
+                    .last_mut()
+                    .ok_or_else(|| err("continuation without a parent"))?;
+            }
+            parent.children.push(line);
+        }
+
+        let mut rules: Vec<(i64, Line)> = roots
+            .into_iter()
+            .filter(|line| line.offset.is_some() && line.test.is_some())
+            .map(|line| (line.strength(), line))
+            .collect();
+        rules.sort_by_key(|(strength, _)| Reverse(*strength));
+        for (_, line) in rules {
+            self.add_magic(BUILTIN_PRIORITY, Arc::new(line));
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when a libmagic rule file is malformed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLibmagicError {
+    line: usize,
+    reason: &'static str,
+}
+
+impl ParseLibmagicError {
+    /// Returns the 1-based number of the malformed line.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl Display for ParseLibmagicError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl Error for ParseLibmagicError {}
+
+/// A rule line and its continuations. Unsupported offsets or tests are
+/// `None` and never match.
+#[derive(Debug)]
+struct Line {
+    offset: Option<Offset>,
+    test: Option<Test>,
+    mime: Option<MimeType>,
+    strength_adjust: Option<(u8, i64)>,
+    children: Vec<Line>,
+}
+
+impl Line {
+    /// Returns the line that annotations on the next line apply to.
+    fn last_descendant(&mut self) -> &mut Line {
+        let mut line = self;
+        while !line.children.is_empty() {
+            line = line.children.last_mut().expect("children are not empty");
+        }
+        line
+    }
+
+    /// Matches this line at `base`, the end of the parent's match, and
+    /// returns the first MIME type found on it or its continuations.
+    fn evaluate(&self, bytes: &[u8], base: usize) -> Option<Option<MimeType>> {
+        let offset = self.offset.as_ref()?.resolve(bytes, base)?;
+        let end = self.test.as_ref()?.matches(bytes, offset)?;
+        let mut mime = self.mime.clone();
+        for child in &self.children {
+            if let Some(found) = child.evaluate(bytes, end)
+                && mime.is_none()
+            {
+                mime = found;
+            }
+        }
+        Some(mime)
+    }
+
+    /// Approximates libmagic's strength, which orders rules by how specific
+    /// their first test is.
+    fn strength(&self) -> i64 {
+        let mut strength = 2 * STRENGTH_UNIT;
+        let Some(test) = &self.test else {
+            return 0;
+        };
+        let (len, op) = match test {
+            Test::Numeric { size, op, .. } => (*size as i64 * STRENGTH_UNIT, *op),
+            Test::String { value, op, .. } => {
+                ((value.len() as i64).saturating_mul(STRENGTH_UNIT), *op)
+            }
+            Test::Search { value, .. } => {
+                let len = value.len() as i64;
+                (len * (STRENGTH_UNIT / len.max(1)).max(1), b'=')
+            }
+            Test::Regex { literal_len, .. } => {
+                let len = *literal_len as i64;
+                (len * (STRENGTH_UNIT / len.max(1)).max(1), b'=')
+            }
+        };
+        strength = strength.saturating_add(len);
+        match op {
+            b'=' => strength += STRENGTH_UNIT,
+            b'<' | b'>' => strength -= 2 * STRENGTH_UNIT,
+            b'!' | b'&' | b'^' => strength -= STRENGTH_UNIT,
+            b'x' => strength = 0,
+            _ => {}
+        }
+        match self.strength_adjust {
+            Some((b'+', n)) => strength.saturating_add(n),
+            Some((b'-', n)) => strength.saturating_sub(n),
+            Some((b'*', n)) => strength.saturating_mul(n),
+            Some((b'/', n)) => strength.checked_div(n).unwrap_or(strength),
+            _ => strength,
+        }
+    }
+}
+
+impl Sniffer for Line {
+    fn sniff(&self, bytes: &[u8]) -> Option<MimeType> {
+        self.evaluate(bytes, 0).flatten()
+    }
+}
+
+/// Where a test reads its value.
+#[derive(Debug)]
+struct Offset {
+    /// Relative to the end of the parent's match (`&`).
+    relative: bool,
+    base: Base,
+}
+
+#[derive(Debug)]
+enum Base {
+    /// A fixed offset; negative values count from the end of the data.
+    Direct(i64),
+    /// An offset read from the data, as in `(0x3c.l+4)`.
+    Indirect {
+        relative: bool,
+        at: i64,
+        size: usize,
+        big_endian: bool,
+        signed: bool,
+        op: u8,
+        operand: i64,
+    },
+}
+
+impl Offset {
+    fn resolve(&self, bytes: &[u8], base: usize) -> Option<usize> {
+        let origin = if self.relative {
+            i64::try_from(base).ok()?
+        } else {
+            0
+        };
+        let value = match self.base {
+            Base::Direct(offset) if offset < 0 && !self.relative => bytes.len() as i64 + offset,
+            Base::Direct(offset) => offset,
+            Base::Indirect {
+                relative,
+                at,
+                size,
+                big_endian,
+                signed,
+                op,
+                operand,
+            } => {
+                let at = if relative {
+                    i64::try_from(base).ok()?.checked_add(at)?
+                } else {
+                    at
+                };
+                let raw = read_uint(bytes, usize::try_from(at).ok()?, size, big_endian)?;
+                let value = if signed {
+                    sign_extend(raw, size)
+                } else {
+                    raw as i64
+                };
+                match op {
+                    b'+' => value.checked_add(operand)?,
+                    b'-' => value.checked_sub(operand)?,
+                    b'*' => value.checked_mul(operand)?,
+                    b'/' => value.checked_div(operand)?,
+                    _ => value,
+                }
+            }
+        };
+        usize::try_from(origin.checked_add(value)?).ok()
+    }
+}
+
+#[derive(Debug)]
+enum Test {
+    Numeric {
+        size: usize,
+        big_endian: bool,
+        signed: bool,
+        mask: Option<u64>,
+        op: u8,
+        value: u64,
+    },
+    String {
+        value: Vec<u8>,
+        op: u8,
+        case_insensitive: bool,
+    },
+    Search {
+        value: Vec<u8>,
+        range: usize,
+        case_insensitive: bool,
+    },
+    Regex {
+        regex: Regex,
+        literal_len: usize,
+        limit: usize,
+        lines: bool,
+    },
+}
+
+impl Test {
+    /// Returns the end of the match if the test passes at `offset`.
+    fn matches(&self, bytes: &[u8], offset: usize) -> Option<usize> {
+        match self {
+            Test::Numeric {
+                size,
+                big_endian,
+                signed,
+                mask,
+                op,
+                value,
+            } => {
+                let mut actual = read_uint(bytes, offset, *size, *big_endian)?;
+                if let Some(mask) = mask {
+                    actual &= mask;
+                }
+                let expected = *value & width_mask(*size);
+                let ordering = if *signed {
+                    sign_extend(actual, *size).cmp(&sign_extend(expected, *size))
+                } else {
+                    actual.cmp(&expected)
+                };
+                let passed = match op {
+                    b'=' => actual == expected,
+                    b'!' => actual != expected,
+                    b'<' => ordering.is_lt(),
+                    b'>' => ordering.is_gt(),
+                    b'&' => actual & expected == expected,
+                    b'^' => actual & expected == 0,
+                    _ => true,
+                };
+                passed.then_some(offset + size)
+            }
+            Test::String {
+                value,
+                op,
+                case_insensitive,
+            } => {
+                if *op == b'x' {
+                    return (offset <= bytes.len()).then_some(offset);
+                }
+                let window = bytes.get(offset..)?;
+                let window = &window[..value.len().min(window.len())];
+                let ordering = if *case_insensitive {
+                    window
+                        .iter()
+                        .map(u8::to_ascii_lowercase)
+                        .cmp(value.iter().map(u8::to_ascii_lowercase))
+                } else {
+                    window.cmp(value.as_slice())
+                };
+                let passed = match op {
+                    b'!' => ordering.is_ne(),
+                    b'<' => ordering.is_lt(),
+                    b'>' => ordering.is_gt(),
+                    _ => ordering.is_eq(),
+                };
+                passed.then_some(offset + value.len())
+            }
+            Test::Search {
+                value,
+                range,
+                case_insensitive,
+            } => {
+                let end = bytes
+                    .len()
+                    .min(offset.saturating_add(range.saturating_add(value.len())));
+                let window = bytes.get(offset..end)?;
+                let found = window.windows(value.len().max(1)).position(|candidate| {
+                    if *case_insensitive {
+                        candidate.eq_ignore_ascii_case(value)
+                    } else {
+                        candidate == value.as_slice()
+                    }
+                })?;
+                Some(offset + found + value.len())
+            }
+            Test::Regex {
+                regex,
+                limit,
+                lines,
+                ..
+            } => {
+                let window = bytes.get(offset..)?;
+                let max = if *lines {
+                    limit.saturating_mul(REGEX_LINE_LEN)
+                } else {
+                    *limit
+                };
+                let max = match max {
+                    0 => DEFAULT_SEARCH_LEN,
+                    max => max.min(DEFAULT_SEARCH_LEN),
+                };
+                let window = &window[..window.len().min(max)];
+                let len = if *lines {
+                    window
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, b)| **b == b'\n')
+                        .nth(limit.saturating_sub(1))
+                        .map_or(window.len(), |(idx, _)| idx + 1)
+                } else {
+                    window.len()
+                };
+                let (_, end) = regex.find(&window[..len])?;
+                Some(offset + end)
+            }
+        }
+    }
+}
+
+/// Splits a rule line into whitespace-separated fields, keeping escaped
+/// whitespace inside a field.
+struct Fields<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Fields<'a> {
+    fn next(&mut self) -> Option<&'a str> {
+        let rest = self.rest.trim_start();
+        if rest.is_empty() {
+            return None;
+        }
+        let mut escaped = false;
+        let end = rest
+            .char_indices()
+            .find(|(_, c)| {
+                let ends = !escaped && c.is_whitespace();
+                escaped = !escaped && *c == '\\';
+                ends
+            })
+            .map_or(rest.len(), |(idx, _)| idx);
+        self.rest = &rest[end..];
+        Some(&rest[..end])
+    }
+}
+
+/// Parses an offset such as `12`, `&4`, `-8` or `(0x3c.l+4)`.
+fn parse_offset(field: &str) -> Option<Offset> {
+    let (relative, field) = match field.strip_prefix('&') {
+        Some(rest) => (true, rest),
+        None => (false, field),
+    };
+    let Some(inner) = field.strip_prefix('(') else {
+        return Some(Offset {
+            relative,
+            base: Base::Direct(parse_int(field)?),
+        });
+    };
+    let inner = inner.strip_suffix(')')?;
+    let (indirect_relative, inner) = match inner.strip_prefix('&') {
+        Some(rest) => (true, rest),
+        None => (false, inner),
+    };
+    let split = inner
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| matches!(c, '.' | ',' | '+' | '-' | '*' | '/'))
+        .map_or(inner.len(), |(idx, _)| idx);
+    let at = parse_int(&inner[..split])?;
+    let mut rest = &inner[split..];
+
+    let (mut size, mut big_endian, mut signed) = (4, false, false);
+    if let Some(spec) = rest.strip_prefix('.').or_else(|| rest.strip_prefix(',')) {
+        signed = rest.starts_with(',');
+        let kind = spec.chars().next()?;
+        (size, big_endian) = match kind {
+            'b' | 'c' | 'B' | 'C' => (1, false),
+            's' | 'h' => (2, false),
+            'S' | 'H' => (2, true),
+            'l' => (4, false),
+            'L' => (4, true),
+            'q' => (8, false),
+            'Q' => (8, true),
+            _ => return None,
+        };
+        rest = &spec[1..];
+    }
+    let (op, operand) = match rest.chars().next() {
+        None => (0, 0),
+        Some(op @ ('+' | '-' | '*' | '/')) => (op as u8, parse_int(&rest[1..])?),
+        Some(_) => return None,
+    };
+    Some(Offset {
+        relative,
+        base: Base::Indirect {
+            relative: indirect_relative,
+            at,
+            size,
+            big_endian,
+            signed,
+            op,
+            operand,
+        },
+    })
+}
+
+/// Parses the type and test fields into a test.
+fn parse_test(kind: &str, test: &str) -> Option<Test> {
+    let (kind, mask) = match kind.split_once('&') {
+        Some((kind, mask)) => (kind, Some(parse_int(mask)? as u64)),
+        None => (kind, None),
+    };
+    let mut parts = kind.split('/');
+    let name = parts.next()?;
+    let mut number = None;
+    let mut flags = String::new();
+    for part in parts {
+        let digits = part.bytes().take_while(u8::is_ascii_digit).count();
+        if digits > 0 {
+            number = Some(part[..digits].parse().ok()?);
+        }
+        flags.push_str(&part[digits..]);
+    }
+    let case_insensitive = flags.contains(['c', 'C']);
+
+    let numeric = |size, big_endian, signed| {
+        let (op, value) = split_op(test, true);
+        let value = if op == b'x' {
+            0
+        } else {
+            parse_int(value)? as u64
+        };
+        Some(Test::Numeric {
+            size,
+            big_endian,
+            signed,
+            mask,
+            op,
+            value,
+        })
+    };
+    let native = cfg!(target_endian = "big");
+    let (signed, name) = match name.strip_prefix('u') {
+        Some(name) => (false, name),
+        None => (true, name),
+    };
+    match name {
+        "byte" => numeric(1, native, signed),
+        "short" => numeric(2, native, signed),
+        "long" => numeric(4, native, signed),
+        "quad" => numeric(8, native, signed),
+        "leshort" => numeric(2, false, signed),
+        "lelong" => numeric(4, false, signed),
+        "lequad" => numeric(8, false, signed),
+        "beshort" => numeric(2, true, signed),
+        "belong" => numeric(4, true, signed),
+        "bequad" => numeric(8, true, signed),
+        "string" if signed => {
+            let (op, value) = split_op(test, false);
+            Some(Test::String {
+                value: unescape(value)?,
+                op,
+                case_insensitive,
+            })
+        }
+        "search" if signed => Some(Test::Search {
+            value: unescape(test.strip_prefix('=').unwrap_or(test))?,
+            range: number.unwrap_or(DEFAULT_SEARCH_LEN),
+            case_insensitive,
+        }),
+        "regex" if signed => {
+            let pattern =
+                String::from_utf8(unescape(test.strip_prefix('=').unwrap_or(test))?).ok()?;
+            Some(Test::Regex {
+                literal_len: pattern
+                    .bytes()
+                    .filter(|b| b.is_ascii_alphanumeric() || *b == b' ')
+                    .count(),
+                regex: Regex::new(&pattern, case_insensitive).ok()?,
+                limit: number.unwrap_or(DEFAULT_SEARCH_LEN),
+                lines: flags.contains('l'),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Splits a leading comparison operator off a test value; `x` matches
+/// anything.
+fn split_op(test: &str, numeric: bool) -> (u8, &str) {
+    if test == "x" {
+        return (b'x', "");
+    }
+    let ops: &[u8] = if numeric { b"=!<>&^" } else { b"=!<>" };
+    match test.bytes().next() {
+        Some(op) if ops.contains(&op) && test.len() > 1 => (op, &test[1..]),
+        _ => (b'=', test),
+    }
+}
+
+/// Decodes the backslash escapes of a string test.
+fn unescape(value: &str) -> Option<Vec<u8>> {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] != b'\\' {
+            out.push(bytes[idx]);
+            idx += 1;
+            continue;
+        }
+        idx += 1;
+        let escape = *bytes.get(idx)?;
+        idx += 1;
+        match escape {
+            b'n' => out.push(b'\n'),
+            b'r' => out.push(b'\r'),
+            b't' => out.push(b'\t'),
+            b'a' => out.push(0x07),
+            b'b' => out.push(0x08),
+            b'f' => out.push(0x0c),
+            b'v' => out.push(0x0b),
+            b'x' => {
+                let len = bytes[idx..]
+                    .iter()
+                    .take(2)
+                    .take_while(|b| b.is_ascii_hexdigit())
+                    .count();
+                let hex = std::str::from_utf8(&bytes[idx..idx + len]).ok()?;
+                out.push(u8::from_str_radix(hex, 16).ok()?);
+                idx += len;
+            }
+            b'0'..=b'7' => {
+                let len = 1 + bytes[idx..]
+                    .iter()
+                    .take(2)
+                    .take_while(|b| (b'0'..=b'7').contains(b))
+                    .count();
+                let octal = std::str::from_utf8(&bytes[idx - 1..idx - 1 + len]).ok()?;
+                out.push(u16::from_str_radix(octal, 8).ok()? as u8);
+                idx += len - 1;
+            }
+            other => out.push(other),
+        }
+    }
+    Some(out)
+}
+
+/// Parses a C-style integer: decimal, `0x` hexadecimal or `0` octal, with
+/// an optional sign and integer suffixes.
+fn parse_int(field: &str) -> Option<i64> {
+    let field = field.trim_end_matches(['L', 'l', 'U', 'u']);
+    let (negative, digits) = match field.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, field.strip_prefix('+').unwrap_or(field)),
+    };
+    let value = if let Some(hex) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        u64::from_str_radix(hex, 16).ok()? as i64
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        i64::from_str_radix(&digits[1..], 8).ok()?
+    } else {
+        digits.parse().ok()?
+    };
+    Some(if negative {
+        value.wrapping_neg()
+    } else {
+        value
+    })
+}
+
+/// Parses a `!:strength` adjustment such as `+20` or `* 2`.
+fn parse_strength(value: &str) -> Option<(u8, i64)> {
+    let op = *value.as_bytes().first()?;
+    if !b"+-*/".contains(&op) {
+        return None;
+    }
+    Some((op, parse_int(value[1..].trim())?))
+}
+
+/// Reads an unsigned integer of `size` bytes at `offset`.
+fn read_uint(bytes: &[u8], offset: usize, size: usize, big_endian: bool) -> Option<u64> {
+    let field = bytes.get(offset..offset.checked_add(size)?)?;
+    let fold = |acc: u64, b: &u8| (acc << 8) | *b as u64;
+    Some(if big_endian {
+        field.iter().fold(0, fold)
+    } else {
+        field.iter().rev().fold(0, fold)
+    })
+}
+
+fn width_mask(size: usize) -> u64 {
+    if size >= 8 {
+        u64::MAX
+    } else {
+        (1 << (size * 8)) - 1
+    }
+}
+
+fn sign_extend(value: u64, size: usize) -> i64 {
+    let shift = 64 - size as u32 * 8;
+    ((value << shift) as i64) >> shift
+}
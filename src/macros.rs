@@ -0,0 +1,94 @@
+/// Declares a MIME type category from a single table of formats.
+///
+/// Each entry lists the variant, its canonical MIME string, alternative MIME
+/// strings that `from_mime` also accepts, and its file extensions with the
+/// preferred one first. The enum, `ALL`, `Display`, `MimeFormat` and the
+/// `EXTENSIONS`/`MIME_TYPES` lookup tables are all generated from it, so they
+/// cannot drift apart. The category name must match the `MimeType` variant.
+macro_rules! mime_category {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident {
+                    mime: $mime:literal,
+                    aliases: [$($alias:literal),* $(,)?],
+                    extensions: [$($ext:literal),+ $(,)?] $(,)?
+                }
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )*
+        }
+
+        impl $name {
+            #[doc = concat!("All `", stringify!($name), "` formats, in declaration order.")]
+            pub const ALL: &'static [$name] = &[$($name::$variant),*];
+
+            /// Returns all file extensions for this format, preferred extension first.
+            pub fn extensions(&self) -> &'static [&'static str] {
+                match self {
+                    $($name::$variant => &[$($ext),+],)*
+                }
+            }
+
+            /// Returns the preferred file extension for this format.
+            pub fn preferred_extension(&self) -> &'static str {
+                self.extensions()[0]
+            }
+
+            /// Returns the alternative MIME strings accepted for this format
+            /// besides its canonical one.
+            pub fn aliases(&self) -> &'static [&'static str] {
+                match self {
+                    $($name::$variant => &[$($alias),*],)*
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                let mime_str = match self {
+                    $($name::$variant => $mime,)*
+                };
+                write!(f, "{}", mime_str)
+            }
+        }
+
+        /// File extensions and the formats they map to, in lookup order.
+        pub(crate) const EXTENSIONS: &[(&str, $name)] = &[
+            $($(($ext, $name::$variant),)+)*
+        ];
+
+        /// MIME strings and the formats they map to, in lookup order.
+        ///
+        /// Canonical strings come first so that aliases never shadow them.
+        pub(crate) const MIME_TYPES: &[(&str, $name)] = &[
+            $(($mime, $name::$variant),)*
+            $($(($alias, $name::$variant),)*)*
+        ];
+
+        impl $crate::MimeFormat for $name {
+            fn from_ext(ext: &str) -> Option<$crate::MimeType> {
+                EXTENSIONS
+                    .iter()
+                    .find(|(key, _)| *key == ext)
+                    .map(|(_, format)| $crate::MimeType::$name(format.clone()))
+            }
+
+            fn from_mime(mime: &str) -> Option<$crate::MimeType> {
+                MIME_TYPES
+                    .iter()
+                    .find(|(key, _)| *key == mime)
+                    .map(|(_, format)| $crate::MimeType::$name(format.clone()))
+            }
+        }
+    };
+}
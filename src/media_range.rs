@@ -0,0 +1,142 @@
+use crate::{MediaType, MimeType, ParseMediaTypeError};
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// A media range pattern such as `image/*`, `*/*` or `application/pdf`.
+///
+/// Media ranges follow the grammar of RFC 9110 (section 12.5.1): either the
+/// subtype or both the type and subtype may be the `*` wildcard. Parameters
+/// are kept and must all be present on a media type for it to match.
+///
+/// # Examples
+///
+/// ```
+/// use mime_type::{Archive, Image, MediaRange, MimeType};
+///
+/// let images: MediaRange = "image/*".parse().unwrap();
+/// assert!(images.matches(&MimeType::Image(Image::Png)));
+/// assert!(!images.matches(&MimeType::Archive(Archive::Pdf)));
+///
+/// let pdf: MediaRange = "application/pdf".parse().unwrap();
+/// assert!(pdf.matches(&MimeType::Archive(Archive::Pdf)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaRange {
+    media: MediaType,
+}
+
+impl MediaRange {
+    /// Parses a media range string.
+    ///
+    /// Equivalent to `s.parse::<MediaRange>()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` is not a valid media range, including a
+    /// wildcard type with a concrete subtype such as `*/html`.
+    pub fn parse(s: &str) -> Result<MediaRange, ParseMediaTypeError> {
+        let media = MediaType::parse(s)?;
+        if media.type_() == "*" && media.subtype() != "*" {
+            return Err(ParseMediaTypeError::InvalidType);
+        }
+        Ok(MediaRange { media })
+    }
+
+    /// Returns the range matching every media type, `*/*`.
+    pub fn any() -> MediaRange {
+        MediaRange::parse("*/*").expect("*/* is a valid media range")
+    }
+
+    /// Returns the top-level type, or `*` for the full wildcard.
+    pub fn type_(&self) -> &str {
+        self.media.type_()
+    }
+
+    /// Returns the subtype, or `*` for a wildcard subtype.
+    pub fn subtype(&self) -> &str {
+        self.media.subtype()
+    }
+
+    /// Returns the value of the first parameter with the given name.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.media.param(name)
+    }
+
+    /// Returns all parameters as `(name, value)` pairs in their original order.
+    pub fn params(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.media.params()
+    }
+
+    /// Removes and returns the parameters from index `at` onwards.
+    pub(crate) fn split_off_params(&mut self, at: usize) -> Vec<(String, String)> {
+        self.media.split_off_params(at)
+    }
+
+    /// Returns `true` if the type or subtype is a wildcard.
+    pub fn is_wildcard(&self) -> bool {
+        self.subtype() == "*"
+    }
+
+    /// Returns how specific this range is, for precedence between ranges.
+    ///
+    /// `*/*` is 0, `type/*` is 1 and `type/subtype` is 2, plus one for each
+    /// parameter, so `text/plain;format=flowed` outranks `text/plain`.
+    pub fn specificity(&self) -> usize {
+        let wildcards = match (self.type_(), self.subtype()) {
+            ("*", _) => 0,
+            (_, "*") => 1,
+            _ => 2,
+        };
+        wildcards + self.params().count()
+    }
+
+    /// Checks whether a MIME type falls within this range.
+    ///
+    /// The type and subtype are taken from the `Display` output of the MIME
+    /// type. Only [`MimeType::Other`] carries parameters, so a range with
+    /// parameters never matches the known formats.
+    pub fn matches(&self, mime: &MimeType) -> bool {
+        self.matches_media_type(&MediaType::from(mime))
+    }
+
+    /// Checks whether a media type falls within this range.
+    ///
+    /// Every parameter of the range must be present on `media` with the same
+    /// value; additional parameters on `media` are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{MediaRange, MediaType};
+    ///
+    /// let range = MediaRange::parse("text/*; charset=utf-8").unwrap();
+    /// let media = MediaType::parse("text/html; charset=utf-8; level=1").unwrap();
+    /// assert!(range.matches_media_type(&media));
+    ///
+    /// let media = MediaType::parse("text/html").unwrap();
+    /// assert!(!range.matches_media_type(&media));
+    /// ```
+    pub fn matches_media_type(&self, media: &MediaType) -> bool {
+        let type_matches = self.type_() == "*" || self.type_() == media.type_();
+        let subtype_matches = self.subtype() == "*" || self.subtype() == media.subtype();
+        type_matches
+            && subtype_matches
+            && self
+                .params()
+                .all(|(name, value)| media.param(name) == Some(value))
+    }
+}
+
+impl FromStr for MediaRange {
+    type Err = ParseMediaTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        MediaRange::parse(s)
+    }
+}
+
+impl Display for MediaRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.media)
+    }
+}
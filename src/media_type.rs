@@ -0,0 +1,297 @@
+use crate::MimeType;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// A structured media type with optional parameters.
+///
+/// Parses strings such as `text/html; charset=utf-8` following the grammar of
+/// RFC 9110 (section 8.3.1) and RFC 6838. The type, subtype and parameter names
+/// are case-insensitive and stored in lowercase; parameter values keep their
+/// case and have quoted-string escapes removed.
+///
+/// # Examples
+///
+/// ```
+/// use mime_type::{Image, MediaType, MimeType};
+///
+/// let media: MediaType = "Image/PNG; charset=binary".parse().unwrap();
+/// assert_eq!(media.essence(), "image/png");
+/// assert_eq!(media.param("charset"), Some("binary"));
+/// assert_eq!(media.to_mime_type(), Some(MimeType::Image(Image::Png)));
+/// assert_eq!(media.to_string(), "image/png; charset=binary");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaType {
+    type_: String,
+    subtype: String,
+    params: Vec<(String, String)>,
+}
+
+impl MediaType {
+    /// Parses a media type string.
+    ///
+    /// Equivalent to `s.parse::<MediaType>()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `s` is not a syntactically valid media type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{MediaType, ParseMediaTypeError};
+    ///
+    /// let media = MediaType::parse(r#"text/plain;title="a \"b\"""#).unwrap();
+    /// assert_eq!(media.param("title"), Some(r#"a "b""#));
+    ///
+    /// assert_eq!(MediaType::parse("text"), Err(ParseMediaTypeError::MissingSubtype));
+    /// ```
+    pub fn parse(s: &str) -> Result<MediaType, ParseMediaTypeError> {
+        let s = s.trim_matches(is_whitespace);
+        let (essence, params) = match s.find(';') {
+            Some(idx) => (&s[..idx], &s[idx..]),
+            None => (s, ""),
+        };
+        let essence = essence.trim_end_matches(is_whitespace);
+        let (type_, subtype) = essence
+            .split_once('/')
+            .ok_or(ParseMediaTypeError::MissingSubtype)?;
+        if !is_token(type_) {
+            return Err(ParseMediaTypeError::InvalidType);
+        }
+        if !is_token(subtype) {
+            return Err(ParseMediaTypeError::InvalidSubtype);
+        }
+
+        Ok(MediaType {
+            type_: type_.to_ascii_lowercase(),
+            subtype: subtype.to_ascii_lowercase(),
+            params: parse_params(params)?,
+        })
+    }
+
+    /// Returns the top-level type, e.g. `image` for `image/svg+xml`.
+    pub fn type_(&self) -> &str {
+        &self.type_
+    }
+
+    /// Returns the subtype including any suffix, e.g. `svg+xml` for `image/svg+xml`.
+    pub fn subtype(&self) -> &str {
+        &self.subtype
+    }
+
+    /// Returns the structured syntax suffix, e.g. `xml` for `image/svg+xml`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::MediaType;
+    ///
+    /// let media = MediaType::parse("application/epub+zip").unwrap();
+    /// assert_eq!(media.suffix(), Some("zip"));
+    ///
+    /// let media = MediaType::parse("application/pdf").unwrap();
+    /// assert_eq!(media.suffix(), None);
+    /// ```
+    pub fn suffix(&self) -> Option<&str> {
+        self.subtype
+            .rsplit_once('+')
+            .map(|(_, suffix)| suffix)
+            .filter(|suffix| !suffix.is_empty())
+    }
+
+    /// Returns the type and subtype without parameters, e.g. `text/html`.
+    pub fn essence(&self) -> String {
+        format!("{}/{}", self.type_, self.subtype)
+    }
+
+    /// Returns the value of the first parameter with the given name.
+    ///
+    /// Parameter names are matched case-insensitively.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns all parameters as `(name, value)` pairs in their original order.
+    pub fn params(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Removes and returns the parameters from index `at` onwards.
+    pub(crate) fn split_off_params(&mut self, at: usize) -> Vec<(String, String)> {
+        self.params.split_off(at)
+    }
+
+    /// Resolves the essence of this media type to a known [`MimeType`].
+    ///
+    /// Parameters are ignored. Returns `None` if the type is not recognized.
+    pub fn to_mime_type(&self) -> Option<MimeType> {
+        crate::known_from_mime(&self.essence())
+    }
+}
+
+impl FromStr for MediaType {
+    type Err = ParseMediaTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        MediaType::parse(s)
+    }
+}
+
+impl From<&MimeType> for MediaType {
+    fn from(mime: &MimeType) -> Self {
+        if let MimeType::Other(media) = mime {
+            return media.clone();
+        }
+        let mime = mime.to_string();
+        let (type_, subtype) = mime.split_once('/').unwrap_or((&mime, ""));
+        MediaType {
+            type_: type_.to_string(),
+            subtype: subtype.to_string(),
+            params: Vec::new(),
+        }
+    }
+}
+
+impl Display for MediaType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.type_, self.subtype)?;
+        write_params(f, &self.params)
+    }
+}
+
+/// Error returned when parsing a media type fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseMediaTypeError {
+    /// The `/` separating type and subtype is missing.
+    MissingSubtype,
+    /// The type is empty or contains characters outside the token grammar.
+    InvalidType,
+    /// The subtype is empty or contains characters outside the token grammar.
+    InvalidSubtype,
+    /// A parameter is not of the form `name=value`.
+    InvalidParameter,
+    /// A quoted parameter value is missing its closing quote.
+    UnterminatedQuotedString,
+    /// A quality value (`q=`) is not a number between 0 and 1 with at most
+    /// three decimals.
+    InvalidQuality,
+}
+
+impl Display for ParseMediaTypeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            ParseMediaTypeError::MissingSubtype => "missing '/' between type and subtype",
+            ParseMediaTypeError::InvalidType => "invalid type",
+            ParseMediaTypeError::InvalidSubtype => "invalid subtype",
+            ParseMediaTypeError::InvalidParameter => "invalid parameter",
+            ParseMediaTypeError::UnterminatedQuotedString => "unterminated quoted string",
+            ParseMediaTypeError::InvalidQuality => "invalid quality value",
+        };
+        write!(f, "{}", msg)
+    }
+}
+
+impl Error for ParseMediaTypeError {}
+
+fn is_whitespace(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+/// Checks a character against the `tchar` rule of RFC 9110.
+pub(crate) fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)
+}
+
+pub(crate) fn is_token(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(is_token_char)
+}
+
+/// Parses the `*( OWS ";" OWS [ parameter ] )` tail of a media type.
+///
+/// Parameter names are lowercased; quoted values are unescaped.
+pub(crate) fn parse_params(s: &str) -> Result<Vec<(String, String)>, ParseMediaTypeError> {
+    let mut params = Vec::new();
+    let mut rest = s.trim_start_matches(is_whitespace);
+    while !rest.is_empty() {
+        rest = rest
+            .strip_prefix(';')
+            .ok_or(ParseMediaTypeError::InvalidParameter)?
+            .trim_start_matches(is_whitespace);
+        if rest.is_empty() || rest.starts_with(';') {
+            continue;
+        }
+
+        let name_end = rest.find(|c| !is_token_char(c)).unwrap_or(rest.len());
+        let name = &rest[..name_end];
+        rest = rest[name_end..]
+            .strip_prefix('=')
+            .ok_or(ParseMediaTypeError::InvalidParameter)?;
+        if name.is_empty() {
+            return Err(ParseMediaTypeError::InvalidParameter);
+        }
+
+        let value = if let Some(quoted) = rest.strip_prefix('"') {
+            let (value, len) = parse_quoted(quoted)?;
+            rest = &quoted[len..];
+            value
+        } else {
+            let value_end = rest.find(|c| !is_token_char(c)).unwrap_or(rest.len());
+            let value = &rest[..value_end];
+            if value.is_empty() {
+                return Err(ParseMediaTypeError::InvalidParameter);
+            }
+            rest = &rest[value_end..];
+            value.to_string()
+        };
+
+        params.push((name.to_ascii_lowercase(), value));
+        rest = rest.trim_start_matches(is_whitespace);
+    }
+    Ok(params)
+}
+
+/// Parses a quoted-string body (after the opening quote).
+///
+/// Returns the unescaped value and the number of bytes consumed, including
+/// the closing quote.
+fn parse_quoted(s: &str) -> Result<(String, usize), ParseMediaTypeError> {
+    let mut value = String::new();
+    let mut chars = s.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, idx + 1)),
+            '\\' => match chars.next() {
+                Some((_, escaped)) => value.push(escaped),
+                None => break,
+            },
+            _ => value.push(c),
+        }
+    }
+    Err(ParseMediaTypeError::UnterminatedQuotedString)
+}
+
+/// Writes parameters as `; name=value`, quoting values that are not tokens.
+pub(crate) fn write_params(f: &mut Formatter<'_>, params: &[(String, String)]) -> fmt::Result {
+    for (name, value) in params {
+        if is_token(value) {
+            write!(f, "; {}={}", name, value)?;
+        } else {
+            write!(f, "; {}=\"", name)?;
+            for c in value.chars() {
+                if c == '"' || c == '\\' {
+                    write!(f, "\\")?;
+                }
+                write!(f, "{}", c)?;
+            }
+            write!(f, "\"")?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use crate::{MimeRegistry, MimeType, ParseMediaTypeError};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter, Write};
+
+/// Column at which extensions start in generated `mime.types` files.
+const EXTENSION_COLUMN: usize = 48;
+
+impl MimeRegistry {
+    /// Creates a registry from an Apache-style `mime.types` file, layered
+    /// over the built-in tables.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a line does not start with a valid media type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{Image, MimeRegistry, MimeType};
+    ///
+    /// let registry = MimeRegistry::from_mime_types(
+    ///     "# comment\n\
+    ///      image/jpeg\t\tjpeg jpg jpe\n\
+    ///      model/gltf+json\t\tgltf\n",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(registry.from_ext("jpe"), Some(MimeType::Image(Image::Jpeg)));
+    /// assert_eq!(registry.from_ext("gltf").unwrap().to_string(), "model/gltf+json");
+    /// ```
+    pub fn from_mime_types(contents: &str) -> Result<MimeRegistry, ParseMimeTypesError> {
+        let mut registry = MimeRegistry::new();
+        registry.load_mime_types(contents)?;
+        Ok(registry)
+    }
+
+    /// Loads the mappings of an Apache-style `mime.types` file.
+    ///
+    /// Each line holds a media type followed by whitespace-separated file
+    /// extensions; `#` starts a comment. This is the format of
+    /// `/etc/mime.types` and the files shipped with Apache. When an
+    /// extension is listed more than once, the last line wins. Extensions that
+    /// already resolve to a type with the same MIME string keep that type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a line does not start with a valid media type.
+    /// Lines before the invalid one have already been loaded.
+    pub fn load_mime_types(&mut self, contents: &str) -> Result<(), ParseMimeTypesError> {
+        for (idx, line) in contents.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            let mut fields = line.split_whitespace();
+            let Some(mime) = fields.next() else {
+                continue;
+            };
+            let mime_type = self
+                .register(mime, &[])
+                .map_err(|error| ParseMimeTypesError {
+                    line: idx + 1,
+                    error,
+                })?;
+            let mime_str = mime_type.to_string();
+            for ext in fields {
+                // Keep mappings that already carry this MIME string, such as
+                // `otf` when `ttf` and `otf` share one line
+                if self
+                    .from_ext(ext)
+                    .is_none_or(|known| known.to_string() != mime_str)
+                {
+                    self.register_ext(ext, mime_type.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the mappings of this registry as an Apache-style `mime.types`
+    /// file.
+    ///
+    /// Built-in types come first, in the order of [`MimeType::iter`],
+    /// followed by custom types. Each extension is listed under the type it
+    /// resolves to in this registry, so loading the output with
+    /// [`MimeRegistry::from_mime_types`] gives the same extension mappings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::MimeRegistry;
+    ///
+    /// let output = MimeRegistry::new().to_mime_types();
+    /// assert!(output.lines().any(|line| {
+    ///     line.split_whitespace().eq(["image/jpeg", "jpg", "jpeg"])
+    /// }));
+    /// ```
+    pub fn to_mime_types(&self) -> String {
+        let mut entries: Vec<(String, Vec<String>)> = Vec::new();
+        let mut add = |mime: &MimeType, ext: Option<&str>| {
+            let mime = mime.to_string();
+            let idx = match entries.iter().position(|(key, _)| *key == mime) {
+                Some(idx) => idx,
+                None => {
+                    entries.push((mime, Vec::new()));
+                    entries.len() - 1
+                }
+            };
+            if let Some(ext) = ext
+                && !entries[idx].1.iter().any(|known| known == ext)
+            {
+                entries[idx].1.push(ext.to_string());
+            }
+        };
+
+        for (ext, _) in MimeType::iter_extensions().filter(|_| self.builtin) {
+            if let Some(resolved) = self.from_ext(ext) {
+                add(&resolved, Some(ext));
+            }
+        }
+        let mut custom: Vec<(&String, &MimeType)> = self.extensions.iter().collect();
+        custom.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        for (ext, mime) in custom {
+            add(mime, Some(ext));
+        }
+        let mut mimes: Vec<(&String, &MimeType)> = self.mimes.iter().collect();
+        mimes.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        for (_, mime) in mimes {
+            add(mime, None);
+        }
+
+        let mut output = String::new();
+        for (mime, extensions) in entries {
+            if extensions.is_empty() {
+                let _ = writeln!(output, "{}", mime);
+            } else {
+                let _ = writeln!(
+                    output,
+                    "{:<width$} {}",
+                    mime,
+                    extensions.join(" "),
+                    width = EXTENSION_COLUMN - 1
+                );
+            }
+        }
+        output
+    }
+}
+
+/// Error returned when a `mime.types` file contains an invalid line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMimeTypesError {
+    line: usize,
+    error: ParseMediaTypeError,
+}
+
+impl ParseMimeTypesError {
+    /// Returns the 1-based number of the invalid line.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the error for the media type on that line.
+    pub fn error(&self) -> &ParseMediaTypeError {
+        &self.error
+    }
+}
+
+impl Display for ParseMimeTypesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+impl Error for ParseMimeTypesError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
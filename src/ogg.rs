@@ -0,0 +1,150 @@
+use crate::{Audio, MediaType, MimeType, Video};
+
+/// Header type flag of the first page of a logical bitstream.
+const BEGINNING_OF_STREAM: u8 = 0x02;
+
+/// A codec carried in an Ogg bitstream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OggCodec {
+    /// Vorbis audio.
+    Vorbis,
+    /// Opus audio.
+    Opus,
+    /// FLAC audio.
+    Flac,
+    /// Speex speech audio.
+    Speex,
+    /// Theora video.
+    Theora,
+}
+
+impl OggCodec {
+    /// Identifies a codec from the first packet of a logical bitstream.
+    fn from_packet(packet: &[u8]) -> Option<OggCodec> {
+        let codec = if packet.starts_with(b"\x01vorbis") {
+            OggCodec::Vorbis
+        } else if packet.starts_with(b"OpusHead") {
+            OggCodec::Opus
+        } else if packet.starts_with(b"\x7FFLAC") {
+            OggCodec::Flac
+        } else if packet.starts_with(b"Speex   ") {
+            OggCodec::Speex
+        } else if packet.starts_with(b"\x80theora") {
+            OggCodec::Theora
+        } else {
+            return None;
+        };
+        Some(codec)
+    }
+
+    /// Returns the name used in the `codecs` parameter (RFC 5334).
+    pub fn name(self) -> &'static str {
+        match self {
+            OggCodec::Vorbis => "vorbis",
+            OggCodec::Opus => "opus",
+            OggCodec::Flac => "flac",
+            OggCodec::Speex => "speex",
+            OggCodec::Theora => "theora",
+        }
+    }
+
+    /// Returns `true` for video codecs.
+    pub fn is_video(self) -> bool {
+        self == OggCodec::Theora
+    }
+}
+
+/// The codecs of the logical bitstreams in an Ogg file.
+///
+/// Every logical bitstream starts with a beginning-of-stream page whose first
+/// packet identifies the codec, and these pages all precede the data pages.
+/// Streams with other codecs, such as Skeleton metadata, are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use mime_type::{Audio, MimeType, Ogg, OggCodec};
+///
+/// let page = b"OggS\0\x02\0\0\0\0\0\0\0\0\x01\0\0\0\0\0\0\0\0\0\0\0\x01\x13OpusHead\x01\x02\0\0\x80\xBB\0\0\0\0\0";
+/// let ogg = Ogg::parse(page).unwrap();
+/// assert_eq!(ogg.codecs(), &[OggCodec::Opus]);
+/// assert_eq!(ogg.mime_type(), MimeType::Audio(Audio::Opus));
+/// assert_eq!(ogg.codecs_parameter().as_deref(), Some("opus"));
+/// assert_eq!(ogg.media_type().to_string(), "audio/opus; codecs=opus");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ogg {
+    codecs: Vec<OggCodec>,
+}
+
+impl Ogg {
+    /// Parses the beginning-of-stream pages of an Ogg file.
+    ///
+    /// Returns `None` if the bytes do not start with an Ogg page.
+    pub fn parse(bytes: &[u8]) -> Option<Ogg> {
+        if !bytes.starts_with(b"OggS") {
+            return None;
+        }
+
+        let mut codecs = Vec::new();
+        let mut pos = 0;
+        while let Some(header) = bytes.get(pos..pos + 27) {
+            if &header[..4] != b"OggS" || header[5] & BEGINNING_OF_STREAM == 0 {
+                break;
+            }
+            let segments = usize::from(header[26]);
+            let Some(lacing) = bytes.get(pos + 27..pos + 27 + segments) else {
+                break;
+            };
+            let data = pos + 27 + segments;
+            let codec = bytes.get(data..).and_then(OggCodec::from_packet);
+            if let Some(codec) = codec
+                && !codecs.contains(&codec)
+            {
+                codecs.push(codec);
+            }
+            pos = data + lacing.iter().map(|&len| usize::from(len)).sum::<usize>();
+        }
+        Some(Ogg { codecs })
+    }
+
+    /// Returns the codecs found, in stream order and without duplicates.
+    pub fn codecs(&self) -> &[OggCodec] {
+        &self.codecs
+    }
+
+    /// Maps the codecs to a MIME type.
+    ///
+    /// Files with a video stream are `video/ogg`, Opus-only files are
+    /// `audio/opus`, and everything else is `audio/ogg`.
+    pub fn mime_type(&self) -> MimeType {
+        if self.codecs.iter().any(|codec| codec.is_video()) {
+            MimeType::Video(Video::Ogv)
+        } else if !self.codecs.is_empty()
+            && self.codecs.iter().all(|&codec| codec == OggCodec::Opus)
+        {
+            MimeType::Audio(Audio::Opus)
+        } else {
+            MimeType::Audio(Audio::Ogg)
+        }
+    }
+
+    /// Returns the value of the `codecs` parameter, e.g. `theora, vorbis`, or
+    /// `None` if no codec was identified.
+    pub fn codecs_parameter(&self) -> Option<String> {
+        if self.codecs.is_empty() {
+            return None;
+        }
+        let names: Vec<&str> = self.codecs.iter().map(|codec| codec.name()).collect();
+        Some(names.join(", "))
+    }
+
+    /// Returns the MIME type with the `codecs` parameter, if any.
+    pub fn media_type(&self) -> MediaType {
+        let mut media = self.mime_type().to_string();
+        if let Some(codecs) = self.codecs_parameter() {
+            media.push_str(&format!("; codecs=\"{}\"", codecs));
+        }
+        MediaType::parse(&media).expect("codec names form a valid media type")
+    }
+}
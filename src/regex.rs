@@ -0,0 +1,496 @@
+use std::ops::Range;
+
+/// Upper bound on compiled instructions and on the nodes compiled to emit
+/// them, which limits `{m,n}` expansion, also of empty groups.
+const MAX_INSTS: usize = 10_000;
+
+/// Upper bound on the nesting of groups and stacked quantifiers.
+const MAX_DEPTH: usize = 100;
+
+/// A compiled POSIX-style regular expression for loaded magic rules.
+///
+/// Supports literals, `.`, bracket expressions with ranges and `[:class:]`
+/// names, `^` and `$` anchors at line boundaries, groups, alternation, and
+/// the `*`, `+`, `?` and `{m,n}` quantifiers. Patterns are compiled to a
+/// Thompson NFA and simulated in one pass without backtracking, so matching
+/// takes time proportional to the input length times the pattern size.
+#[derive(Debug, Clone)]
+pub(crate) struct Regex {
+    insts: Vec<Inst>,
+    case_insensitive: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Inst {
+    Byte(u8),
+    Any,
+    Class(Box<[bool; 256]>),
+    LineStart,
+    LineEnd,
+    Split(usize, usize),
+    Jump(usize),
+    Match,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Byte(u8),
+    Any,
+    Class(Box<[bool; 256]>),
+    LineStart,
+    LineEnd,
+    Concat(Vec<Node>),
+    Alternate(Vec<Node>),
+    Repeat(Box<Node>, u32, Option<u32>),
+}
+
+impl Regex {
+    /// Compiles a pattern, returning a reason if it is malformed or uses
+    /// unsupported syntax such as back-references.
+    pub(crate) fn new(pattern: &str, case_insensitive: bool) -> Result<Regex, &'static str> {
+        let mut parser = Parser {
+            pattern: pattern.as_bytes(),
+            pos: 0,
+            groups: 0,
+        };
+        let (node, _) = parser.alternate()?;
+        if parser.pos < parser.pattern.len() {
+            return Err("unmatched ')'");
+        }
+        let mut insts = Vec::new();
+        let mut budget = MAX_INSTS;
+        compile(&node, &mut insts, &mut budget)?;
+        insts.push(Inst::Match);
+        Ok(Regex {
+            insts,
+            case_insensitive,
+        })
+    }
+
+    /// Finds the leftmost match and returns its start and end.
+    ///
+    /// Among the matches at that position, alternatives and repetitions are
+    /// preferred in pattern order: `*` and `+` match as much as they can and
+    /// `a|ab` matches `a`.
+    pub(crate) fn find(&self, haystack: &[u8]) -> Option<(usize, usize)> {
+        self.find_starting_in(haystack, 0..haystack.len() + 1)
+    }
+
+    /// Finds the leftmost match that starts within `starts`.
+    pub(crate) fn find_starting_in(
+        &self,
+        haystack: &[u8],
+        starts: Range<usize>,
+    ) -> Option<(usize, usize)> {
+        self.run(haystack, starts, false)
+    }
+
+    /// Returns whether the expression matches the whole haystack.
+    pub(crate) fn is_full_match(&self, haystack: &[u8]) -> bool {
+        self.run(haystack, 0..1, true).is_some()
+    }
+
+    /// Simulates the NFA in a single pass over the haystack, starting a new
+    /// thread at every position in `starts` until a match is found.
+    ///
+    /// Threads are kept in priority order, earlier starts first, so the first
+    /// thread to reach `Match` is the leftmost-first match and the threads
+    /// behind it are dropped. With `full`, only a match ending at the end of
+    /// the haystack counts.
+    fn run(&self, haystack: &[u8], starts: Range<usize>, full: bool) -> Option<(usize, usize)> {
+        let starts = starts.start..starts.end.min(haystack.len() + 1);
+        if starts.is_empty() {
+            return None;
+        }
+        let mut visited = vec![false; self.insts.len()];
+        let mut current = Vec::new();
+        let mut found = None;
+        let mut pos = starts.start;
+        loop {
+            if found.is_none() && starts.contains(&pos) {
+                self.add_thread(&mut current, &mut visited, 0, pos, haystack, pos);
+            }
+            if current.is_empty() && (found.is_some() || pos + 1 >= starts.end) {
+                return found;
+            }
+            visited.iter_mut().for_each(|seen| *seen = false);
+            let mut next = Vec::new();
+            for &(pc, start) in &current {
+                if let Inst::Match = self.insts[pc] {
+                    if !full || pos == haystack.len() {
+                        found = Some((start, pos));
+                        break;
+                    }
+                } else if pos < haystack.len() && self.advances(&self.insts[pc], haystack[pos]) {
+                    self.add_thread(&mut next, &mut visited, pc + 1, start, haystack, pos + 1);
+                }
+            }
+            if pos == haystack.len() {
+                return found;
+            }
+            current = next;
+            pos += 1;
+        }
+    }
+
+    /// Returns whether a consuming instruction accepts `byte`.
+    fn advances(&self, inst: &Inst, byte: u8) -> bool {
+        match inst {
+            Inst::Byte(expected) if self.case_insensitive => byte.eq_ignore_ascii_case(expected),
+            Inst::Byte(expected) => byte == *expected,
+            Inst::Any => byte != b'\n',
+            Inst::Class(set) if self.case_insensitive => {
+                set[byte.to_ascii_lowercase() as usize] || set[byte.to_ascii_uppercase() as usize]
+            }
+            Inst::Class(set) => set[byte as usize],
+            _ => false,
+        }
+    }
+
+    /// Adds `pc` and everything reachable from it without consuming input.
+    fn add_thread(
+        &self,
+        threads: &mut Vec<(usize, usize)>,
+        visited: &mut [bool],
+        pc: usize,
+        start: usize,
+        haystack: &[u8],
+        pos: usize,
+    ) {
+        let mut pending = vec![pc];
+        while let Some(pc) = pending.pop() {
+            if visited[pc] {
+                continue;
+            }
+            visited[pc] = true;
+            match self.insts[pc] {
+                Inst::Jump(target) => pending.push(target),
+                Inst::Split(first, second) => {
+                    pending.push(second);
+                    pending.push(first);
+                }
+                Inst::LineStart => {
+                    if pos == 0 || haystack[pos - 1] == b'\n' {
+                        pending.push(pc + 1);
+                    }
+                }
+                Inst::LineEnd => {
+                    if pos == haystack.len() || haystack[pos] == b'\n' {
+                        pending.push(pc + 1);
+                    }
+                }
+                _ => threads.push((pc, start)),
+            }
+        }
+    }
+}
+
+/// Emits the instructions for a node, counting each compiled node against
+/// `budget` so that repeating zero-width nodes cannot run unbounded.
+fn compile(node: &Node, insts: &mut Vec<Inst>, budget: &mut usize) -> Result<(), &'static str> {
+    *budget = budget.checked_sub(1).ok_or("pattern too large")?;
+    if insts.len() > MAX_INSTS {
+        return Err("pattern too large");
+    }
+    match node {
+        Node::Byte(byte) => insts.push(Inst::Byte(*byte)),
+        Node::Any => insts.push(Inst::Any),
+        Node::Class(set) => insts.push(Inst::Class(set.clone())),
+        Node::LineStart => insts.push(Inst::LineStart),
+        Node::LineEnd => insts.push(Inst::LineEnd),
+        Node::Concat(nodes) => {
+            for node in nodes {
+                compile(node, insts, budget)?;
+            }
+        }
+        Node::Alternate(alternatives) => {
+            let mut jumps = Vec::new();
+            for (idx, alternative) in alternatives.iter().enumerate() {
+                let split = insts.len();
+                let is_last = idx + 1 == alternatives.len();
+                if !is_last {
+                    insts.push(Inst::Split(split + 1, 0));
+                }
+                compile(alternative, insts, budget)?;
+                if !is_last {
+                    jumps.push(insts.len());
+                    insts.push(Inst::Jump(0));
+                    let next = insts.len();
+                    insts[split] = Inst::Split(split + 1, next);
+                }
+            }
+            let end = insts.len();
+            for jump in jumps {
+                insts[jump] = Inst::Jump(end);
+            }
+        }
+        Node::Repeat(node, min, max) => {
+            for _ in 0..*min {
+                compile(node, insts, budget)?;
+            }
+            match max {
+                None => {
+                    let split = insts.len();
+                    insts.push(Inst::Split(split + 1, 0));
+                    compile(node, insts, budget)?;
+                    insts.push(Inst::Jump(split));
+                    let end = insts.len();
+                    insts[split] = Inst::Split(split + 1, end);
+                }
+                Some(max) => {
+                    let mut splits = Vec::new();
+                    for _ in *min..*max {
+                        splits.push(insts.len());
+                        insts.push(Inst::Split(insts.len() + 1, 0));
+                        compile(node, insts, budget)?;
+                    }
+                    let end = insts.len();
+                    for split in splits {
+                        insts[split] = Inst::Split(split + 1, end);
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+struct Parser<'a> {
+    pattern: &'a [u8],
+    pos: usize,
+    /// Number of currently open groups.
+    groups: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.pattern.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.pos += 1;
+        Some(byte)
+    }
+
+    // The parsing functions return a node with its nesting depth.
+
+    fn alternate(&mut self) -> Result<(Node, usize), &'static str> {
+        let (first, mut depth) = self.concat()?;
+        let mut alternatives = vec![first];
+        while self.peek() == Some(b'|') {
+            self.pos += 1;
+            let (alternative, alternative_depth) = self.concat()?;
+            alternatives.push(alternative);
+            depth = depth.max(alternative_depth);
+        }
+        Ok(if alternatives.len() == 1 {
+            (alternatives.remove(0), depth)
+        } else {
+            (Node::Alternate(alternatives), depth)
+        })
+    }
+
+    fn concat(&mut self) -> Result<(Node, usize), &'static str> {
+        let mut nodes = Vec::new();
+        let mut depth = 0;
+        while let Some(byte) = self.peek() {
+            if byte == b'|' || byte == b')' {
+                break;
+            }
+            let (atom, atom_depth) = self.atom()?;
+            let (node, node_depth) = self.quantifier(atom, atom_depth)?;
+            nodes.push(node);
+            depth = depth.max(node_depth);
+        }
+        Ok((Node::Concat(nodes), depth))
+    }
+
+    fn atom(&mut self) -> Result<(Node, usize), &'static str> {
+        if self.peek() == Some(b'(') {
+            self.pos += 1;
+            self.groups += 1;
+            if self.groups > MAX_DEPTH {
+                return Err("groups nested too deeply");
+            }
+            let (node, depth) = self.alternate()?;
+            if self.next() != Some(b')') {
+                return Err("unmatched '('");
+            }
+            self.groups -= 1;
+            return Ok((node, depth + 1));
+        }
+        Ok((self.simple_atom()?, 1))
+    }
+
+    fn simple_atom(&mut self) -> Result<Node, &'static str> {
+        match self.next() {
+            Some(b'[') => self.class(),
+            Some(b'.') => Ok(Node::Any),
+            Some(b'^') => Ok(Node::LineStart),
+            Some(b'$') => Ok(Node::LineEnd),
+            Some(b'\\') => self.escape(),
+            Some(b'*' | b'+' | b'?') => Err("quantifier without operand"),
+            Some(byte) => Ok(Node::Byte(byte)),
+            None => Err("unexpected end of pattern"),
+        }
+    }
+
+    fn escape(&mut self) -> Result<Node, &'static str> {
+        let byte = self.next().ok_or("trailing backslash")?;
+        let class = |predicate: fn(u8) -> bool, negated: bool| {
+            let mut set = Box::new([false; 256]);
+            for (idx, member) in set.iter_mut().enumerate() {
+                *member = predicate(idx as u8) != negated;
+            }
+            Node::Class(set)
+        };
+        Ok(match byte {
+            b'd' => class(|b| b.is_ascii_digit(), false),
+            b'D' => class(|b| b.is_ascii_digit(), true),
+            b's' => class(|b| b.is_ascii_whitespace(), false),
+            b'S' => class(|b| b.is_ascii_whitespace(), true),
+            b'w' => class(is_word, false),
+            b'W' => class(is_word, true),
+            b'n' => Node::Byte(b'\n'),
+            b'r' => Node::Byte(b'\r'),
+            b't' => Node::Byte(b'\t'),
+            b'1'..=b'9' => return Err("back-references are not supported"),
+            _ => Node::Byte(byte),
+        })
+    }
+
+    fn class(&mut self) -> Result<Node, &'static str> {
+        let mut set = Box::new([false; 256]);
+        let negated = self.peek() == Some(b'^');
+        if negated {
+            self.pos += 1;
+        }
+        let mut first = true;
+        loop {
+            let byte = self.next().ok_or("unterminated bracket expression")?;
+            match byte {
+                b']' if !first => break,
+                b'[' if self.peek() == Some(b':') => {
+                    let rest = &self.pattern[self.pos + 1..];
+                    let len = rest
+                        .windows(2)
+                        .position(|w| w == b":]")
+                        .ok_or("unterminated character class")?;
+                    let predicate: fn(u8) -> bool = match &rest[..len] {
+                        b"alpha" => |b| b.is_ascii_alphabetic(),
+                        b"digit" => |b| b.is_ascii_digit(),
+                        b"alnum" => |b| b.is_ascii_alphanumeric(),
+                        b"upper" => |b| b.is_ascii_uppercase(),
+                        b"lower" => |b| b.is_ascii_lowercase(),
+                        b"space" => |b| b.is_ascii_whitespace() || b == 0x0b,
+                        b"blank" => |b| b == b' ' || b == b'\t',
+                        b"xdigit" => |b| b.is_ascii_hexdigit(),
+                        b"punct" => |b| b.is_ascii_punctuation(),
+                        b"print" => |b| b.is_ascii_graphic() || b == b' ',
+                        b"graph" => |b| b.is_ascii_graphic(),
+                        b"cntrl" => |b| b.is_ascii_control(),
+                        _ => return Err("unknown character class"),
+                    };
+                    for (idx, member) in set.iter_mut().enumerate() {
+                        *member |= predicate(idx as u8);
+                    }
+                    self.pos += len + 3;
+                }
+                _ => {
+                    let is_range = self.peek() == Some(b'-')
+                        && self
+                            .pattern
+                            .get(self.pos + 1)
+                            .is_some_and(|&end| end != b']');
+                    if is_range {
+                        let end = self.pattern[self.pos + 1];
+                        if end < byte {
+                            return Err("invalid range");
+                        }
+                        self.pos += 2;
+                        for member in &mut set[byte as usize..=end as usize] {
+                            *member = true;
+                        }
+                    } else {
+                        set[byte as usize] = true;
+                    }
+                }
+            }
+            first = false;
+        }
+        if negated {
+            for member in set.iter_mut() {
+                *member = !*member;
+            }
+        }
+        Ok(Node::Class(set))
+    }
+
+    /// Applies the quantifiers following an atom. Stacked quantifiers such as
+    /// `a**` apply to the repetition.
+    fn quantifier(
+        &mut self,
+        mut node: Node,
+        mut depth: usize,
+    ) -> Result<(Node, usize), &'static str> {
+        while let Some((min, max)) = self.repetition()? {
+            depth += 1;
+            if depth > MAX_DEPTH {
+                return Err("quantifiers nested too deeply");
+            }
+            node = Node::Repeat(Box::new(node), min, max);
+        }
+        Ok((node, depth))
+    }
+
+    /// Parses a quantifier, if one follows, into its bounds.
+    fn repetition(&mut self) -> Result<Option<(u32, Option<u32>)>, &'static str> {
+        let (min, max) = match self.peek() {
+            Some(b'*') => (0, None),
+            Some(b'+') => (1, None),
+            Some(b'?') => (0, Some(1)),
+            Some(b'{')
+                if self
+                    .pattern
+                    .get(self.pos + 1)
+                    .is_some_and(u8::is_ascii_digit) =>
+            {
+                self.pos += 1;
+                let min = self.number()?;
+                let max = if self.peek() == Some(b',') {
+                    self.pos += 1;
+                    if self.peek() == Some(b'}') {
+                        None
+                    } else {
+                        Some(self.number()?)
+                    }
+                } else {
+                    Some(min)
+                };
+                if self.peek() != Some(b'}') || max.is_some_and(|max| max < min) {
+                    return Err("invalid repetition");
+                }
+                (min, max)
+            }
+            _ => return Ok(None),
+        };
+        self.pos += 1;
+        Ok(Some((min, max)))
+    }
+
+    fn number(&mut self) -> Result<u32, &'static str> {
+        let len = self.pattern[self.pos..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        let digits = std::str::from_utf8(&self.pattern[self.pos..self.pos + len])
+            .map_err(|_| "invalid repetition")?;
+        self.pos += len;
+        digits.parse().map_err(|_| "invalid repetition")
+    }
+}
+
+fn is_word(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
@@ -0,0 +1,473 @@
+use crate::regex::Regex;
+use crate::{Archive, MediaType, MimeFormat, MimeType, ParseMediaTypeError, Text, signature, zip};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Priority of the built-in signatures relative to loaded magic rules.
+///
+/// Matches the default priority of shared-mime-info, so loaded rules with a
+/// higher priority are tried first and those with a lower one only when no
+/// built-in signature matches.
+pub(crate) const BUILTIN_PRIORITY: u32 = 50;
+
+/// A lookup table for custom extensions and MIME strings.
+///
+/// A registry starts out with the built-in tables behind
+/// [`MimeFormat::from_ext`] and [`MimeFormat::from_mime`] and layers custom
+/// mappings on top of them. Custom mappings take precedence, so they can also
+/// override built-in ones. Each registry is an independent value; there is no
+/// global state.
+///
+/// Extensions and MIME strings are matched case-insensitively.
+///
+/// # Examples
+///
+/// ```
+/// use mime_type::{Archive, MimeRegistry, MimeType};
+///
+/// let mut registry = MimeRegistry::new();
+/// let scene = registry.register("application/x-scene", &["scene"]).unwrap();
+/// assert_eq!(registry.from_ext("scene"), Some(scene));
+///
+/// // Override a built-in mapping for this registry only
+/// registry.register_ext("pdf", MimeType::Archive(Archive::Zip));
+/// assert_eq!(registry.from_ext("pdf"), Some(MimeType::Archive(Archive::Zip)));
+///
+/// // Built-in mappings remain available
+/// assert!(registry.from_ext("png").is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MimeRegistry {
+    pub(crate) builtin: bool,
+    pub(crate) extensions: HashMap<String, MimeType>,
+    pub(crate) mimes: HashMap<String, MimeType>,
+    pub(crate) globs: Vec<Glob>,
+    pub(crate) magic: Vec<MagicRule>,
+    pub(crate) subclasses: HashMap<String, Vec<String>>,
+}
+
+/// A file name pattern such as `*.tar.gz` or `Makefile`.
+#[derive(Debug, Clone)]
+pub(crate) struct Glob {
+    pub(crate) pattern: String,
+    pub(crate) weight: u32,
+    pub(crate) case_sensitive: bool,
+    /// Set for patterns that are regular expressions rather than globs.
+    pub(crate) regex: Option<Regex>,
+    pub(crate) mime: MimeType,
+}
+
+/// A loaded content rule that recognizes a MIME type from leading bytes.
+pub(crate) trait Sniffer: Debug + Send + Sync {
+    fn sniff(&self, bytes: &[u8]) -> Option<MimeType>;
+}
+
+/// A content rule and its priority; higher priorities are tried first.
+#[derive(Debug, Clone)]
+pub(crate) struct MagicRule {
+    pub(crate) priority: u32,
+    pub(crate) sniffer: Arc<dyn Sniffer>,
+}
+
+impl MimeRegistry {
+    /// Creates a registry backed by the built-in tables.
+    pub fn new() -> MimeRegistry {
+        MimeRegistry {
+            builtin: true,
+            extensions: HashMap::new(),
+            mimes: HashMap::new(),
+            globs: Vec::new(),
+            magic: Vec::new(),
+            subclasses: HashMap::new(),
+        }
+    }
+
+    /// Creates a registry without the built-in tables.
+    ///
+    /// Only mappings registered on this registry are resolved.
+    pub fn empty() -> MimeRegistry {
+        MimeRegistry {
+            builtin: false,
+            ..MimeRegistry::new()
+        }
+    }
+
+    /// Maps a file extension to a MIME type.
+    ///
+    /// Returns the previous custom mapping for the extension, if any.
+    pub fn register_ext(&mut self, ext: &str, mime: MimeType) -> Option<MimeType> {
+        self.extensions.insert(normalize_ext(ext), mime)
+    }
+
+    /// Maps a MIME string to a MIME type.
+    ///
+    /// Useful for aliases such as `image/jpg`. Returns the previous custom
+    /// mapping for the MIME string, if any.
+    pub fn register_mime(&mut self, mime: &str, mime_type: MimeType) -> Option<MimeType> {
+        self.mimes
+            .insert(mime.trim().to_ascii_lowercase(), mime_type)
+    }
+
+    /// Registers a MIME string together with its file extensions.
+    ///
+    /// The MIME string resolves to a known format where one exists and to
+    /// [`MimeType::Other`] otherwise. Returns the resolved MIME type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `mime` is not a valid media type.
+    pub fn register(
+        &mut self,
+        mime: &str,
+        extensions: &[&str],
+    ) -> Result<MimeType, ParseMediaTypeError> {
+        let mime_type = match self.from_mime(mime) {
+            Some(mime_type) => mime_type,
+            None => MimeType::from(MediaType::parse(mime)?),
+        };
+        self.register_mime(mime, mime_type.clone());
+        for ext in extensions {
+            self.register_ext(ext, mime_type.clone());
+        }
+        Ok(mime_type)
+    }
+
+    /// Removes a custom extension mapping, restoring the built-in one.
+    pub fn unregister_ext(&mut self, ext: &str) -> Option<MimeType> {
+        self.extensions.remove(&normalize_ext(ext))
+    }
+
+    /// Removes a custom MIME string mapping, restoring the built-in one.
+    pub fn unregister_mime(&mut self, mime: &str) -> Option<MimeType> {
+        self.mimes.remove(&mime.trim().to_ascii_lowercase())
+    }
+
+    /// Records that `child` is a specialization of `parent`, e.g. that
+    /// `image/svg+xml` is a kind of `application/xml`.
+    ///
+    /// Both MIME strings are resolved through this registry first, so
+    /// aliases work. See [`MimeRegistry::is_subclass`].
+    pub fn register_subclass(&mut self, child: &str, parent: &str) {
+        let child = self.essence_of(child);
+        let parent = self.essence_of(parent);
+        let parents = self.subclasses.entry(child).or_default();
+        if !parents.contains(&parent) {
+            parents.push(parent);
+        }
+    }
+
+    /// Returns whether `child` is `parent` or a specialization of it.
+    ///
+    /// Registered subclass relations are followed transitively. The built-in
+    /// containers count as well, so a DOCX is a kind of ZIP. Every `text/*`
+    /// type is a kind of `text/plain`, and everything is a kind of
+    /// `application/octet-stream`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{Archive, Document, MimeRegistry, MimeType};
+    ///
+    /// let mut registry = MimeRegistry::new();
+    /// let zip = MimeType::Archive(Archive::Zip);
+    /// assert!(registry.is_subclass(&MimeType::Document(Document::Docx), &zip));
+    ///
+    /// let sketch = registry.register("application/x-sketch", &["sketch"]).unwrap();
+    /// registry.register_subclass("application/x-sketch", "application/zip");
+    /// assert!(registry.is_subclass(&sketch, &zip));
+    /// ```
+    pub fn is_subclass(&self, child: &MimeType, parent: &MimeType) -> bool {
+        if child == parent || signature::is_refinement(parent, child) {
+            return true;
+        }
+        let parent = essence(parent);
+        let child = essence(child);
+        if parent == "application/octet-stream"
+            || (parent == Text::Plain.to_string() && child.starts_with("text/"))
+        {
+            return true;
+        }
+
+        let mut pending = vec![child];
+        let mut visited: Vec<String> = Vec::new();
+        while let Some(current) = pending.pop() {
+            if current == parent {
+                return true;
+            }
+            if visited.contains(&current) {
+                continue;
+            }
+            if let Some(parents) = self.subclasses.get(&current) {
+                pending.extend(parents.iter().cloned());
+            }
+            visited.push(current);
+        }
+        false
+    }
+
+    /// Converts a file extension to a MIME type.
+    ///
+    /// Custom mappings are checked first, then the built-in tables. A built-in
+    /// extension that matches exactly wins over one that differs in case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{Archive, MimeRegistry, MimeType};
+    ///
+    /// let registry = MimeRegistry::new();
+    /// assert_eq!(registry.from_ext("Z"), Some(MimeType::Archive(Archive::Z)));
+    /// assert_eq!(registry.from_ext("z"), Some(MimeType::Archive(Archive::Z)));
+    /// assert_eq!(registry.from_ext(".PNG"), registry.from_ext("png"));
+    /// ```
+    pub fn from_ext(&self, ext: &str) -> Option<MimeType> {
+        let ext = ext.trim_start_matches('.');
+        if let Some(mime) = self.extensions.get(&normalize_ext(ext)) {
+            return Some(mime.clone());
+        }
+        if !self.builtin {
+            return None;
+        }
+        MimeType::from_ext(ext).or_else(|| {
+            MimeType::iter_extensions()
+                .find(|(key, _)| key.eq_ignore_ascii_case(ext))
+                .map(|(_, mime)| mime)
+        })
+    }
+
+    /// Converts a MIME string to a MIME type.
+    ///
+    /// Custom mappings are checked first, by the full string and then by its
+    /// essence without parameters, followed by the built-in tables.
+    pub fn from_mime(&self, mime: &str) -> Option<MimeType> {
+        if let Some(mime_type) = self.mimes.get(&mime.trim().to_ascii_lowercase()) {
+            return Some(mime_type.clone());
+        }
+        if let Ok(media) = MediaType::parse(mime)
+            && let Some(mime_type) = self.mimes.get(&media.essence())
+        {
+            return Some(mime_type.clone());
+        }
+        if !self.builtin {
+            return None;
+        }
+        MimeType::from_mime(mime)
+    }
+
+    /// Determines the MIME type from the file name of a path.
+    ///
+    /// Loaded file name patterns such as `*.tar.gz` or `Makefile` are
+    /// checked first, preferring the highest weight and then the longest
+    /// pattern, followed by the extension.
+    pub fn from_path(&self, path: &Path) -> Option<MimeType> {
+        if let Some(name) = path.file_name().and_then(|name| name.to_str())
+            && let Some(glob) = self
+                .globs
+                .iter()
+                .filter(|glob| glob.matches(name))
+                .max_by_key(|glob| (glob.weight, glob.pattern.len()))
+        {
+            return Some(glob.mime.clone());
+        }
+        self.from_ext(path.extension()?.to_str()?)
+    }
+
+    /// Detects the MIME type of file content from its leading bytes.
+    ///
+    /// Loaded magic rules are tried in priority order around the built-in
+    /// signatures: rules with a higher priority than the built-ins come first,
+    /// the rest only when no built-in signature matches. When a loaded rule
+    /// and a built-in signature disagree, the more specific type wins, so a
+    /// DOCX is still reported as DOCX under a generic ZIP rule.
+    pub fn from_bytes(&self, bytes: &[u8]) -> Option<MimeType> {
+        let builtin = if self.builtin {
+            MimeType::from_bytes(bytes)
+        } else {
+            None
+        };
+        for rule in &self.magic {
+            if builtin.is_some() && rule.priority < BUILTIN_PRIORITY {
+                break;
+            }
+            if let Some(found) = rule.sniffer.sniff(bytes) {
+                return match builtin {
+                    Some(builtin) if builtin != found && self.is_subclass(&builtin, &found) => {
+                        Some(builtin)
+                    }
+                    _ => Some(found),
+                };
+            }
+        }
+        builtin
+    }
+
+    /// Detects the MIME type of a file from its content and file name.
+    ///
+    /// Content wins, unless the file name names a more specific type of the
+    /// detected content, e.g. a `.docx` name for ZIP content. As with
+    /// [`MimeType::from_file`], the central directory of ZIP files is read to
+    /// identify the format stored in them.
+    pub fn from_file(&self, path: &Path) -> io::Result<Option<MimeType>> {
+        let mut file = File::open(path)?;
+        let mut header = Vec::with_capacity(signature::HEADER_LEN);
+        file.by_ref()
+            .take(signature::HEADER_LEN as u64)
+            .read_to_end(&mut header)?;
+
+        let mut by_content = self.from_bytes(&header);
+        if by_content == Some(MimeType::Archive(Archive::Zip)) {
+            by_content = zip::detect_file(&mut file, &header)?.or(by_content);
+        }
+        let by_name = self.from_path(path);
+        Ok(match (by_content, by_name) {
+            (Some(content), Some(name)) if self.is_subclass(&name, &content) => Some(name),
+            (Some(content), _) => Some(content),
+            (None, name) => name,
+        })
+    }
+
+    /// Adds a content rule, keeping rules ordered by descending priority.
+    pub(crate) fn add_magic(&mut self, priority: u32, sniffer: Arc<dyn Sniffer>) {
+        let idx = self
+            .magic
+            .iter()
+            .position(|rule| rule.priority < priority)
+            .unwrap_or(self.magic.len());
+        self.magic.insert(idx, MagicRule { priority, sniffer });
+    }
+
+    /// Resolves a MIME string through this registry to its lowercase essence.
+    fn essence_of(&self, mime: &str) -> String {
+        match self.from_mime(mime) {
+            Some(mime_type) => essence(&mime_type),
+            None => mime.trim().to_ascii_lowercase(),
+        }
+    }
+
+    /// Returns all extensions that resolve to a MIME type in this registry.
+    ///
+    /// Custom extensions come first in sorted order, followed by the built-in
+    /// extensions that have not been overridden.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{Image, MimeRegistry, MimeType};
+    ///
+    /// let mut registry = MimeRegistry::new();
+    /// registry.register_ext("jfif", MimeType::Image(Image::Jpeg));
+    /// assert_eq!(
+    ///     registry.extensions(&MimeType::Image(Image::Jpeg)),
+    ///     vec!["jfif", "jpg", "jpeg"]
+    /// );
+    /// ```
+    pub fn extensions(&self, mime: &MimeType) -> Vec<&str> {
+        let mut custom: Vec<&str> = self
+            .extensions
+            .iter()
+            .filter(|(_, mime_type)| *mime_type == mime)
+            .map(|(ext, _)| ext.as_str())
+            .collect();
+        custom.sort_unstable();
+        if self.builtin {
+            custom.extend(
+                mime.extensions()
+                    .iter()
+                    .filter(|ext| !self.extensions.contains_key(&normalize_ext(ext))),
+            );
+        }
+        custom
+    }
+}
+
+impl Default for MimeRegistry {
+    fn default() -> Self {
+        MimeRegistry::new()
+    }
+}
+
+impl Glob {
+    /// Returns whether a file name matches this pattern.
+    fn matches(&self, name: &str) -> bool {
+        if let Some(regex) = &self.regex {
+            regex.is_full_match(name.as_bytes())
+        } else if self.case_sensitive {
+            glob_match(self.pattern.as_bytes(), name.as_bytes())
+        } else {
+            glob_match(
+                self.pattern.to_ascii_lowercase().as_bytes(),
+                name.to_ascii_lowercase().as_bytes(),
+            )
+        }
+    }
+}
+
+/// Normalizes an extension for lookup, dropping a leading dot.
+fn normalize_ext(ext: &str) -> String {
+    ext.trim_start_matches('.').to_ascii_lowercase()
+}
+
+/// Returns the lowercase essence of a MIME type, without parameters.
+fn essence(mime: &MimeType) -> String {
+    MediaType::from(mime).essence()
+}
+
+/// Matches a name against a shell-style pattern with `*`, `?` and `[...]`.
+///
+/// On a mismatch, only the most recent `*` is retried with one more byte, which
+/// keeps matching at O(n·m) regardless of the number of stars.
+fn glob_match(pattern: &[u8], name: &[u8]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    let mut star = None;
+    while n < name.len() {
+        if pattern.get(p) == Some(&b'*') {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some(next) = match_byte(pattern, p, name[n]) {
+            p = next;
+            n += 1;
+        } else if let Some((star_p, star_n)) = star {
+            star = Some((star_p, star_n + 1));
+            p = star_p + 1;
+            n = star_n + 1;
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&b| b == b'*')
+}
+
+/// Matches a single byte against the pattern element at `p`, other than `*`,
+/// returning the index of the next element.
+fn match_byte(pattern: &[u8], p: usize, byte: u8) -> Option<usize> {
+    match *pattern.get(p)? {
+        b'?' => Some(p + 1),
+        b'[' => {
+            let Some(end) = pattern[p..].iter().skip(2).position(|&b| b == b']') else {
+                return (byte == b'[').then_some(p + 1);
+            };
+            let class = &pattern[p + 1..p + end + 2];
+            let (negated, class) = match class.split_first() {
+                Some((b'!' | b'^', class)) => (true, class),
+                _ => (false, class),
+            };
+            let mut found = false;
+            let mut idx = 0;
+            while idx < class.len() {
+                if idx + 2 < class.len() && class[idx + 1] == b'-' {
+                    found |= (class[idx]..=class[idx + 2]).contains(&byte);
+                    idx += 3;
+                } else {
+                    found |= class[idx] == byte;
+                    idx += 1;
+                }
+            }
+            (found != negated).then_some(p + end + 3)
+        }
+        literal => (literal == byte).then_some(p + 1),
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{Audio, Image, MimeType, Video};
+
+/// Chunk size marking a 64-bit size stored in the `ds64` chunk of RF64 files.
+const RF64_SIZE: u32 = 0xFFFF_FFFF;
+
+/// The form type and chunks of a RIFF file.
+///
+/// WAV, AVI, WebP, animated cursors and RIFF MIDI files all start with `RIFF`
+/// and are told apart by the form type at offset 8. WAV files over 4 GiB use
+/// the RF64 or BW64 header instead, with 64-bit sizes in a `ds64` chunk.
+///
+/// Only the given bytes are parsed, so chunks beyond them are not listed.
+///
+/// # Examples
+///
+/// ```
+/// use mime_type::{Image, MimeFormat, MimeType, Riff, WebpFormat};
+///
+/// let webp = b"RIFF\x24\0\0\0WEBPVP8X\x0a\0\0\0\x12\0\0\0\0\0\0\0\0\0";
+/// let riff = Riff::parse(webp).unwrap();
+/// assert_eq!(riff.form_type(), "WEBP");
+/// assert_eq!(riff.chunks(), &["VP8X"]);
+/// assert_eq!(
+///     riff.webp_format(),
+///     Some(WebpFormat::Extended { animation: true, alpha: true })
+/// );
+/// assert_eq!(riff.mime_type(), Some(MimeType::Image(Image::Webp)));
+///
+/// let ani = Riff::parse(b"RIFF\x04\0\0\0ACON").unwrap();
+/// let mime = ani.mime_type().unwrap();
+/// assert_eq!(mime.to_string(), "image/x-navi-animation");
+/// assert_eq!(MimeType::from_mime("application/x-navi-animation"), Some(mime));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Riff {
+    form_type: String,
+    large: bool,
+    chunks: Vec<String>,
+    webp_format: Option<WebpFormat>,
+}
+
+/// The encoding of a WebP image, given by its first chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WebpFormat {
+    /// Lossy VP8 image data (`VP8 ` chunk).
+    Lossy,
+    /// Lossless VP8L image data (`VP8L` chunk).
+    Lossless,
+    /// Extended format (`VP8X` chunk), with flags for animation and alpha.
+    Extended { animation: bool, alpha: bool },
+}
+
+impl Riff {
+    /// Parses the header and chunks of a RIFF, RF64 or BW64 file.
+    ///
+    /// Returns `None` if the bytes do not start with one of these headers.
+    pub fn parse(bytes: &[u8]) -> Option<Riff> {
+        let large = match bytes.get(..4)? {
+            b"RIFF" => false,
+            b"RF64" | b"BW64" => true,
+            _ => return None,
+        };
+        let form_type = fourcc(bytes.get(8..12)?);
+
+        let mut chunks = Vec::new();
+        let mut webp_format = None;
+        let mut data_size = None;
+        let mut pos = 12;
+        while let Some(header) = bytes.get(pos..pos + 8) {
+            let id = &header[..4];
+            let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+            let data = &bytes[pos + 8..];
+            let size = match (id, size) {
+                (b"data", RF64_SIZE) if large => data_size.unwrap_or(u64::MAX),
+                _ => u64::from(size),
+            };
+            if large && id == b"ds64" {
+                // RIFF size, then data size, both 64-bit
+                data_size = data
+                    .get(8..16)
+                    .map(|b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]));
+            }
+            if form_type == "WEBP" && chunks.is_empty() {
+                webp_format = match id {
+                    b"VP8 " => Some(WebpFormat::Lossy),
+                    b"VP8L" => Some(WebpFormat::Lossless),
+                    b"VP8X" => data.first().map(|flags| WebpFormat::Extended {
+                        animation: flags & 0x02 != 0,
+                        alpha: flags & 0x10 != 0,
+                    }),
+                    _ => None,
+                };
+            }
+            chunks.push(fourcc(id));
+            // Chunks are padded to an even size
+            let Some(next) = size
+                .checked_add(size & 1)
+                .and_then(|size| usize::try_from(size).ok())
+                .and_then(|size| (pos + 8).checked_add(size))
+            else {
+                break;
+            };
+            pos = next;
+        }
+
+        Some(Riff {
+            form_type,
+            large,
+            chunks,
+            webp_format,
+        })
+    }
+
+    /// Returns the form type, e.g. `WAVE`, `AVI ` or `WEBP`.
+    pub fn form_type(&self) -> &str {
+        &self.form_type
+    }
+
+    /// Returns `true` for RF64 and BW64 files, which use 64-bit sizes.
+    pub fn is_large(&self) -> bool {
+        self.large
+    }
+
+    /// Returns the IDs of the top-level chunks, in file order.
+    pub fn chunks(&self) -> &[String] {
+        &self.chunks
+    }
+
+    /// Returns the encoding of a WebP image, or `None` for other forms.
+    pub fn webp_format(&self) -> Option<WebpFormat> {
+        self.webp_format
+    }
+
+    /// Maps the form type to a MIME type, or `None` for unknown forms.
+    pub fn mime_type(&self) -> Option<MimeType> {
+        let mime = match self.form_type.as_str() {
+            "WAVE" => MimeType::Audio(Audio::Wav),
+            "AVI " | "AVIX" => MimeType::Video(Video::Avi),
+            "WEBP" => MimeType::Image(Image::Webp),
+            "ACON" => MimeType::Image(Image::Ani),
+            "RMID" => MimeType::Audio(Audio::Midi),
+            _ => return None,
+        };
+        Some(mime)
+    }
+}
+
+fn fourcc(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
@@ -0,0 +1,134 @@
+use crate::{Category, MimeType};
+use std::fmt::Write;
+
+/// Column at which extensions start in the nginx `types` block.
+const NGINX_EXTENSION_COLUMN: usize = 52;
+
+/// Generates Content-Type configuration for web servers from the built-in
+/// extension tables.
+///
+/// Each extension is listed once, under the type [`MimeFormat::from_ext`]
+/// returns for it, and extensions are grouped by MIME string. Output can be
+/// restricted to chosen categories; an extension listed in several
+/// categories then maps to the first allowed one.
+///
+/// [`MimeFormat::from_ext`]: crate::MimeFormat::from_ext
+///
+/// # Examples
+///
+/// ```
+/// use mime_type::{Category, ServerConfig};
+///
+/// let config = ServerConfig::new().categories(&[Category::Image, Category::Font]);
+/// let nginx = config.nginx();
+/// assert!(nginx.starts_with("types {\n"));
+/// assert!(nginx.contains("jpg jpeg;"));
+/// assert!(!nginx.contains("video/mp4"));
+///
+/// assert!(config.apache().contains("AddType image/png .png\n"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ServerConfig {
+    categories: Option<Vec<Category>>,
+}
+
+impl ServerConfig {
+    /// Creates a generator covering every category.
+    pub fn new() -> ServerConfig {
+        ServerConfig { categories: None }
+    }
+
+    /// Restricts the output to the given categories.
+    pub fn categories(mut self, categories: &[Category]) -> ServerConfig {
+        self.categories = Some(categories.to_vec());
+        self
+    }
+
+    /// Returns the MIME strings and their extensions, in table order.
+    fn entries(&self) -> Vec<(String, Vec<&'static str>)> {
+        let mut seen: Vec<&str> = Vec::new();
+        let mut entries: Vec<(String, Vec<&'static str>)> = Vec::new();
+        for (ext, mime) in MimeType::iter_extensions() {
+            let allowed = self
+                .categories
+                .as_ref()
+                .is_none_or(|categories| categories.contains(&mime.category()));
+            if !allowed || seen.contains(&ext) {
+                continue;
+            }
+            seen.push(ext);
+            let mime = mime.to_string();
+            match entries.iter_mut().find(|(key, _)| *key == mime) {
+                Some((_, extensions)) => extensions.push(ext),
+                None => entries.push((mime, vec![ext])),
+            }
+        }
+        entries
+    }
+
+    /// Generates an nginx `types { }` block.
+    ///
+    /// Use it in place of the `include mime.types;` line of `nginx.conf`.
+    pub fn nginx(&self) -> String {
+        let mut output = String::from("types {\n");
+        for (mime, extensions) in self.entries() {
+            let _ = writeln!(
+                output,
+                "    {:<width$} {};",
+                mime,
+                extensions.join(" "),
+                width = NGINX_EXTENSION_COLUMN - 5
+            );
+        }
+        output.push_str("}\n");
+        output
+    }
+
+    /// Generates Apache `AddType` directives, one per MIME string.
+    pub fn apache(&self) -> String {
+        let mut output = String::new();
+        for (mime, extensions) in self.entries() {
+            let _ = write!(output, "AddType {}", mime);
+            for ext in extensions {
+                let _ = write!(output, " .{}", ext);
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Generates the `<staticContent>` element of an IIS `web.config`.
+    ///
+    /// Each `<mimeMap>` is preceded by a `<remove>` for the same extension,
+    /// so the output can replace mappings IIS already inherits.
+    pub fn iis(&self) -> String {
+        let mut output = String::from("<staticContent>\n");
+        for (mime, extensions) in self.entries() {
+            for ext in extensions {
+                let _ = writeln!(output, "  <remove fileExtension=\".{}\" />", ext);
+                let _ = writeln!(
+                    output,
+                    "  <mimeMap fileExtension=\".{}\" mimeType=\"{}\" />",
+                    ext, mime
+                );
+            }
+        }
+        output.push_str("</staticContent>\n");
+        output
+    }
+
+    /// Generates a Caddyfile snippet named `mime_types` that sets the
+    /// `Content-Type` header by path.
+    ///
+    /// Import it in a site block with `import mime_types`.
+    pub fn caddy(&self) -> String {
+        let mut output = String::from("(mime_types) {\n");
+        for (mime, extensions) in self.entries() {
+            let paths: Vec<String> = extensions.iter().map(|ext| format!("*.{}", ext)).collect();
+            let _ = writeln!(output, "\t@{} path {}", extensions[0], paths.join(" "));
+            let _ = writeln!(output, "\theader @{} Content-Type {}", extensions[0], mime);
+        }
+        output.push_str("}\n");
+        output
+    }
+}
@@ -0,0 +1,583 @@
+use crate::regex::Regex;
+use crate::registry::{Glob, Sniffer};
+use crate::signature::HEADER_LEN;
+use crate::{MimeFormat, MimeRegistry, MimeType};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Weight of patterns from the legacy `globs` file, which has none.
+const DEFAULT_WEIGHT: u32 = 50;
+
+/// Header of the compiled `magic` file.
+const MAGIC_HEADER: &[u8] = b"MIME-Magic\0\n";
+
+/// Deepest indent accepted for a magic matchlet, which bounds the nesting of
+/// loaded rules.
+const MAX_INDENT: usize = 64;
+
+impl MimeRegistry {
+    /// Creates a registry from a compiled shared-mime-info database, layered
+    /// over the built-in tables.
+    ///
+    /// See [`MimeRegistry::load_shared_mime_info`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use mime_type::MimeRegistry;
+    /// use std::path::Path;
+    ///
+    /// let registry = MimeRegistry::from_shared_mime_info(Path::new("/usr/share/mime"))?;
+    /// println!("{:?}", registry.from_path(Path::new("backup.tar.gz")));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn from_shared_mime_info(dir: &Path) -> io::Result<MimeRegistry> {
+        let mut registry = MimeRegistry::new();
+        registry.load_shared_mime_info(dir)?;
+        Ok(registry)
+    }
+
+    /// Loads a compiled shared-mime-info database such as `/usr/share/mime`.
+    ///
+    /// Reads the `aliases`, `subclasses`, `globs2` (or the older `globs`) and
+    /// `magic` files written by `update-mime-database`; missing files are
+    /// skipped. Types that match a known format resolve to the existing
+    /// `MimeType` variants, the rest become [`MimeType::Other`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read, or one with
+    /// [`io::ErrorKind::InvalidData`] wrapping a
+    /// [`ParseSharedMimeInfoError`] if a file is malformed.
+    pub fn load_shared_mime_info(&mut self, dir: &Path) -> io::Result<()> {
+        let invalid = |err| io::Error::new(io::ErrorKind::InvalidData, err);
+        if let Some(contents) = read_optional(&dir.join("aliases"))? {
+            self.load_aliases(&contents).map_err(invalid)?;
+        }
+        if let Some(contents) = read_optional(&dir.join("subclasses"))? {
+            self.load_subclasses(&contents).map_err(invalid)?;
+        }
+        if let Some(contents) = read_optional(&dir.join("globs2"))? {
+            self.load_globs2(&contents).map_err(invalid)?;
+        } else if let Some(contents) = read_optional(&dir.join("globs"))? {
+            self.load_globs(&contents).map_err(invalid)?;
+        }
+        match fs::read(dir.join("magic")) {
+            Ok(contents) => self.load_magic(&contents).map_err(invalid),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Loads a shared-mime-info `globs2` file of `weight:type:pattern[:flags]`
+    /// lines.
+    ///
+    /// Plain `*.ext` patterns become extension mappings, where the first and
+    /// thus heaviest one for an extension wins; among equally weighted ones,
+    /// a matching built-in mapping is kept. Other patterns, such as
+    /// `*.tar.gz`, `Makefile` or case-sensitive ones, are matched by
+    /// [`MimeRegistry::from_path`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::MimeRegistry;
+    /// use std::path::Path;
+    ///
+    /// let mut registry = MimeRegistry::new();
+    /// registry
+    ///     .load_globs2(
+    ///         "55:application/x-compressed-tar:*.tar.gz\n\
+    ///          50:text/x-makefile:makefile\n",
+    ///     )
+    ///     .unwrap();
+    /// let tarball = registry.from_path(Path::new("backup.tar.gz")).unwrap();
+    /// assert_eq!(tarball.to_string(), "application/x-compressed-tar");
+    /// assert!(registry.from_path(Path::new("Makefile")).is_some());
+    /// ```
+    pub fn load_globs2(&mut self, contents: &str) -> Result<(), ParseSharedMimeInfoError> {
+        let mut seen = HashMap::new();
+        for (idx, line) in data_lines(contents) {
+            let err = |reason| ParseSharedMimeInfoError::new(idx, reason);
+            let mut fields = line.splitn(4, ':');
+            let (Some(weight), Some(mime), Some(pattern)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(err("expected weight, type and pattern"));
+            };
+            let weight = weight.parse().map_err(|_| err("invalid weight"))?;
+            let case_sensitive = fields
+                .next()
+                .is_some_and(|flags| flags.split(',').any(|flag| flag == "cs"));
+            self.add_glob_line(mime, pattern, weight, case_sensitive, &mut seen)
+                .map_err(err)?;
+        }
+        Ok(())
+    }
+
+    /// Loads a legacy shared-mime-info `globs` file of `type:pattern` lines.
+    ///
+    /// Works like [`MimeRegistry::load_globs2`] with every pattern at the
+    /// default weight.
+    pub fn load_globs(&mut self, contents: &str) -> Result<(), ParseSharedMimeInfoError> {
+        let mut seen = HashMap::new();
+        for (idx, line) in data_lines(contents) {
+            let err = |reason| ParseSharedMimeInfoError::new(idx, reason);
+            let (mime, pattern) = line
+                .split_once(':')
+                .ok_or_else(|| err("expected type and pattern"))?;
+            self.add_glob_line(mime, pattern, DEFAULT_WEIGHT, false, &mut seen)
+                .map_err(err)?;
+        }
+        Ok(())
+    }
+
+    /// Loads a shared-mime-info `aliases` file of `alias type` lines.
+    pub fn load_aliases(&mut self, contents: &str) -> Result<(), ParseSharedMimeInfoError> {
+        for (idx, line) in data_lines(contents) {
+            let err = |reason| ParseSharedMimeInfoError::new(idx, reason);
+            let (alias, mime) = split_pair(line).ok_or_else(|| err("expected alias and type"))?;
+            let mime_type = self
+                .register(mime, &[])
+                .map_err(|_| err("invalid media type"))?;
+            self.register_mime(alias, mime_type);
+        }
+        Ok(())
+    }
+
+    /// Loads a shared-mime-info `subclasses` file of `type parent` lines.
+    ///
+    /// See [`MimeRegistry::is_subclass`].
+    pub fn load_subclasses(&mut self, contents: &str) -> Result<(), ParseSharedMimeInfoError> {
+        for (idx, line) in data_lines(contents) {
+            let (child, parent) = split_pair(line)
+                .ok_or_else(|| ParseSharedMimeInfoError::new(idx, "expected type and parent"))?;
+            self.register_subclass(child, parent);
+        }
+        Ok(())
+    }
+
+    /// Loads a compiled shared-mime-info `magic` file.
+    ///
+    /// Each rule takes part in [`MimeRegistry::from_bytes`] with its
+    /// priority. Masks, word sizes and ranges are supported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file is malformed, or if a priority does not
+    /// fit in a `u32` or an indent is deeper than 64 levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::MimeRegistry;
+    ///
+    /// let mut magic = b"MIME-Magic\0\n[80:application/x-scene]\n>0=\0\x05SCENE\n".to_vec();
+    /// magic.extend_from_slice(b"1>8=\0\x01\x02\n");
+    ///
+    /// let mut registry = MimeRegistry::new();
+    /// registry.load_magic(&magic).unwrap();
+    /// let mime = registry.from_bytes(b"SCENE\0\0\0\x02").unwrap();
+    /// assert_eq!(mime.to_string(), "application/x-scene");
+    /// assert!(registry.from_bytes(b"SCENE\0\0\0\x01").is_none());
+    ///
+    /// // Offsets and ranges past the end of the address space never match
+    /// let magic = b"MIME-Magic\0\n[80:application/x-far]\n>18446744073709551615=\0\x01x+2\n";
+    /// let mut registry = MimeRegistry::empty();
+    /// registry.load_magic(magic).unwrap();
+    /// assert!(registry.from_bytes(b"xxxx").is_none());
+    ///
+    /// // Out-of-range priorities and indents are rejected
+    /// let magic = b"MIME-Magic\0\n[4294967296:application/x-a]\n>0=\0\x01x\n";
+    /// assert!(registry.load_magic(magic).is_err());
+    /// let magic = b"MIME-Magic\0\n[50:application/x-a]\n18446744073709551615>0=\0\x01x\n";
+    /// assert_eq!(registry.load_magic(magic).unwrap_err().line(), 3);
+    /// ```
+    pub fn load_magic(&mut self, contents: &[u8]) -> Result<(), ParseSharedMimeInfoError> {
+        let mut reader = MagicReader {
+            bytes: contents,
+            pos: 0,
+            line: 1,
+        };
+        if !reader.eat(MAGIC_HEADER) {
+            return Err(reader.error("missing MIME-Magic header"));
+        }
+        reader.line += 1;
+
+        while !reader.at_end() {
+            if !reader.eat(b"[") {
+                return Err(reader.error("expected section header"));
+            }
+            let priority = reader.number(b':')?;
+            let priority =
+                u32::try_from(priority).map_err(|_| reader.error("priority out of range"))?;
+            let mime = reader.until(b']')?;
+            let mime = std::str::from_utf8(mime).map_err(|_| reader.error("invalid media type"))?;
+            let mime_type = self
+                .register(mime, &[])
+                .map_err(|_| reader.error("invalid media type"))?;
+            if !reader.eat(b"\n") {
+                return Err(reader.error("expected end of line"));
+            }
+            reader.line += 1;
+
+            let mut flat = Vec::new();
+            while !reader.at_end() && reader.peek() != Some(b'[') {
+                flat.push(reader.matchlet()?);
+            }
+            let mut idx = 0;
+            let matchlets = build_tree(&flat, &mut idx, 0);
+            self.add_magic(
+                priority,
+                Arc::new(MagicEntry {
+                    mime: mime_type,
+                    matchlets,
+                }),
+            );
+        }
+        Ok(())
+    }
+
+    /// Adds one glob pattern for a MIME string.
+    pub(crate) fn add_glob_line(
+        &mut self,
+        mime: &str,
+        pattern: &str,
+        weight: u32,
+        case_sensitive: bool,
+        seen: &mut HashMap<String, u32>,
+    ) -> Result<(), &'static str> {
+        let mime_type = self.register(mime, &[]).map_err(|_| "invalid media type")?;
+        if pattern == "__NOGLOBS__" {
+            self.globs.retain(|glob| glob.mime != mime_type);
+            return Ok(());
+        }
+        if !case_sensitive
+            && let Some(ext) = pattern.strip_prefix("*.")
+            && !ext.is_empty()
+            && !ext.contains(['*', '?', '[', '.'])
+        {
+            let same_type = |known: MimeType| known.to_string() == mime_type.to_string();
+            match seen.get(&ext.to_ascii_lowercase()) {
+                None => {
+                    seen.insert(ext.to_ascii_lowercase(), weight);
+                    // Keep mappings that already carry this MIME string
+                    if !self.from_ext(ext).is_some_and(same_type) {
+                        self.register_ext(ext, mime_type);
+                    }
+                }
+                // Equally weighted patterns are ambiguous; keep the built-in
+                // mapping if it is one of them
+                Some(&first)
+                    if first == weight
+                        && self.builtin
+                        && MimeType::from_ext(&ext.to_ascii_lowercase()).is_some_and(same_type) =>
+                {
+                    self.unregister_ext(ext);
+                }
+                Some(_) => {}
+            }
+            return Ok(());
+        }
+        self.globs.push(Glob {
+            pattern: pattern.to_string(),
+            weight,
+            case_sensitive,
+            regex: None,
+            mime: mime_type,
+        });
+        Ok(())
+    }
+}
+
+/// Error returned when a shared-mime-info file is malformed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSharedMimeInfoError {
+    line: usize,
+    reason: &'static str,
+}
+
+impl ParseSharedMimeInfoError {
+    fn new(line: usize, reason: &'static str) -> Self {
+        ParseSharedMimeInfoError { line, reason }
+    }
+
+    /// Returns the 1-based number of the malformed line.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl Display for ParseSharedMimeInfoError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl Error for ParseSharedMimeInfoError {}
+
+/// A magic rule: the MIME type reported when any of its matchlets match.
+///
+/// Shared with the Tika importer, whose `<magic>` elements use the same model.
+#[derive(Debug)]
+pub(crate) struct MagicEntry {
+    pub(crate) mime: MimeType,
+    pub(crate) matchlets: Vec<Matchlet>,
+}
+
+impl Sniffer for MagicEntry {
+    fn sniff(&self, bytes: &[u8]) -> Option<MimeType> {
+        self.matchlets
+            .iter()
+            .any(|matchlet| matchlet.matches(bytes))
+            .then(|| self.mime.clone())
+    }
+}
+
+/// A single comparison and the comparisons nested under it.
+#[derive(Debug, Clone)]
+pub(crate) struct Matchlet {
+    pub(crate) offset: usize,
+    pub(crate) range: usize,
+    pub(crate) pattern: Pattern,
+    pub(crate) children: Vec<Matchlet>,
+}
+
+/// What a matchlet compares against.
+#[derive(Debug, Clone)]
+pub(crate) enum Pattern {
+    Bytes {
+        value: Vec<u8>,
+        mask: Option<Vec<u8>>,
+    },
+    IgnoreCase(Vec<u8>),
+    /// Matches if a match of the expression starts within the range. Only
+    /// the first [`HEADER_LEN`] bytes are searched, as for the built-in
+    /// signatures.
+    Regex(Regex),
+}
+
+impl Matchlet {
+    /// Matches if the pattern occurs within the range and, when there are
+    /// nested comparisons, any of them matches too.
+    fn matches(&self, bytes: &[u8]) -> bool {
+        let found = match &self.pattern {
+            Pattern::Regex(regex) => bytes[..bytes.len().min(HEADER_LEN)]
+                .get(self.offset..)
+                .and_then(|window| regex.find_starting_in(window, 0..self.range))
+                .is_some(),
+            pattern => {
+                let end = self.offset.saturating_add(self.range).min(bytes.len());
+                (self.offset..end).any(|start| pattern.matches_at(bytes, start))
+            }
+        };
+        found && (self.children.is_empty() || self.children.iter().any(|c| c.matches(bytes)))
+    }
+}
+
+impl Pattern {
+    fn matches_at(&self, bytes: &[u8], start: usize) -> bool {
+        match self {
+            Pattern::Bytes { value, mask } => {
+                let Some(window) = bytes.get(start..).and_then(|rest| rest.get(..value.len()))
+                else {
+                    return false;
+                };
+                match mask {
+                    Some(mask) => window
+                        .iter()
+                        .zip(value)
+                        .zip(mask)
+                        .all(|((byte, value), mask)| byte & mask == value & mask),
+                    None => window == value.as_slice(),
+                }
+            }
+            Pattern::IgnoreCase(value) => bytes
+                .get(start..)
+                .and_then(|rest| rest.get(..value.len()))
+                .is_some_and(|window| window.eq_ignore_ascii_case(value)),
+            Pattern::Regex(_) => false,
+        }
+    }
+}
+
+/// Cursor over the binary `magic` file.
+struct MagicReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> MagicReader<'a> {
+    fn error(&self, reason: &'static str) -> ParseSharedMimeInfoError {
+        ParseSharedMimeInfoError::new(self.line, reason)
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, expected: &[u8]) -> bool {
+        if self.bytes[self.pos..].starts_with(expected) {
+            self.pos += expected.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ParseSharedMimeInfoError> {
+        let taken = self
+            .bytes
+            .get(self.pos..self.pos + len)
+            .ok_or_else(|| self.error("truncated value"))?;
+        self.pos += len;
+        Ok(taken)
+    }
+
+    /// Reads bytes up to and including `end`, returning them without it.
+    fn until(&mut self, end: u8) -> Result<&'a [u8], ParseSharedMimeInfoError> {
+        let len = self.bytes[self.pos..]
+            .iter()
+            .position(|&b| b == end)
+            .ok_or_else(|| self.error("unterminated field"))?;
+        let field = &self.bytes[self.pos..self.pos + len];
+        self.pos += len + 1;
+        Ok(field)
+    }
+
+    /// Reads a decimal number terminated by `end`.
+    fn number(&mut self, end: u8) -> Result<usize, ParseSharedMimeInfoError> {
+        let digits = self.until(end)?;
+        std::str::from_utf8(digits)
+            .ok()
+            .and_then(|digits| digits.parse().ok())
+            .ok_or_else(|| self.error("invalid number"))
+    }
+
+    /// Reads `[indent]>offset=value[&mask][~word-size][+range]\n`.
+    fn matchlet(&mut self) -> Result<(usize, Matchlet), ParseSharedMimeInfoError> {
+        let indent = if self.peek() == Some(b'>') {
+            self.pos += 1;
+            0
+        } else {
+            self.number(b'>')?
+        };
+        if indent > MAX_INDENT {
+            return Err(self.error("indent out of range"));
+        }
+        let offset = self.number(b'=')?;
+        let len = u16::from_be_bytes([self.take(1)?[0], self.take(1)?[0]]) as usize;
+        let mut value = self.take(len)?.to_vec();
+        let mut mask = None;
+        if self.eat(b"&") {
+            mask = Some(self.take(len)?.to_vec());
+        }
+        let mut word_size = 1;
+        let mut range = 1;
+        loop {
+            match self.peek() {
+                Some(b'~') => {
+                    self.pos += 1;
+                    word_size = self.digits()?;
+                }
+                Some(b'+') => {
+                    self.pos += 1;
+                    range = self.digits()?;
+                }
+                Some(b'\n') => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => return Err(self.error("expected end of line")),
+            }
+        }
+        self.line += 1;
+
+        // Host-endian values are stored big-endian
+        if cfg!(target_endian = "little") && matches!(word_size, 2 | 4) {
+            for chunk in value.chunks_mut(word_size) {
+                chunk.reverse();
+            }
+            if let Some(mask) = &mut mask {
+                for chunk in mask.chunks_mut(word_size) {
+                    chunk.reverse();
+                }
+            }
+        }
+
+        Ok((
+            indent,
+            Matchlet {
+                offset,
+                range: range.max(1),
+                pattern: Pattern::Bytes { value, mask },
+                children: Vec::new(),
+            },
+        ))
+    }
+
+    /// Reads a decimal number without a terminator.
+    fn digits(&mut self) -> Result<usize, ParseSharedMimeInfoError> {
+        let len = self.bytes[self.pos..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        let digits = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        std::str::from_utf8(digits)
+            .ok()
+            .and_then(|digits| digits.parse().ok())
+            .ok_or_else(|| self.error("invalid number"))
+    }
+}
+
+/// Nests matchlets under the preceding one with a lower indent. Indents are
+/// at most [`MAX_INDENT`], which bounds the recursion.
+fn build_tree(flat: &[(usize, Matchlet)], idx: &mut usize, indent: usize) -> Vec<Matchlet> {
+    let mut nodes = Vec::new();
+    while let Some((level, matchlet)) = flat.get(*idx) {
+        if *level < indent {
+            break;
+        }
+        *idx += 1;
+        let mut node = matchlet.clone();
+        node.children = build_tree(flat, idx, level + 1);
+        nodes.push(node);
+    }
+    nodes
+}
+
+/// Returns the non-empty, non-comment lines with their 1-based numbers.
+fn data_lines(contents: &str) -> impl Iterator<Item = (usize, &str)> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// Splits a line into two whitespace-separated fields.
+fn split_pair(line: &str) -> Option<(&str, &str)> {
+    let mut fields = line.split_whitespace();
+    let pair = (fields.next()?, fields.next()?);
+    fields.next().is_none().then_some(pair)
+}
+
+/// Reads a file, returning `None` if it does not exist.
+fn read_optional(path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
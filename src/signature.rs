@@ -0,0 +1,660 @@
+use crate::{
+    Application, Archive, Audio, Book, Detection, Document, Ebml, Font, Image, IsoBmff, MimeType,
+    Ogg, Riff, Text, Video, Zip,
+};
+use std::cell::OnceCell;
+
+/// A magic-number signature identifying a file format by its leading bytes.
+///
+/// A signature matches when `magic` appears at `offset` and, if present,
+/// the `refine` check accepts the whole buffer. The refine check only looks at
+/// the first `window` bytes, so its result is final once that many are known.
+struct Signature {
+    offset: usize,
+    magic: &'static [u8],
+    refine: Option<Refine>,
+    window: usize,
+    mime: MimeType,
+}
+
+/// The check a signature makes after its magic matched.
+#[derive(Clone, Copy)]
+enum Refine {
+    /// A check of the leading bytes.
+    Bytes(fn(&[u8]) -> bool),
+    /// Matches if the ZIP entries identify the signature's type.
+    Zip,
+    /// Matches if the OLE directory names a stream of the signature's type.
+    Ole,
+}
+
+/// The leading bytes of a file, with the formats stored in a ZIP or OLE
+/// container identified at most once however many signatures ask.
+struct Header<'a> {
+    bytes: &'a [u8],
+    zip: OnceCell<Option<MimeType>>,
+    ole: OnceCell<Option<MimeType>>,
+}
+
+impl<'a> Header<'a> {
+    /// Keeps the first [`HEADER_LEN`] bytes, which are all the signatures
+    /// look at.
+    fn new(bytes: &'a [u8]) -> Header<'a> {
+        Header {
+            bytes: &bytes[..bytes.len().min(HEADER_LEN)],
+            zip: OnceCell::new(),
+            ole: OnceCell::new(),
+        }
+    }
+
+    /// Returns whether the signature, whose magic matched, applies.
+    fn refine(&self, sig: &Signature) -> bool {
+        let stored = match sig.refine {
+            None => return true,
+            Some(Refine::Bytes(refine)) => return refine(self.bytes),
+            Some(Refine::Zip) => self.zip.get_or_init(|| zip_type(self.bytes)),
+            Some(Refine::Ole) => self.ole.get_or_init(|| ole_type(self.bytes)),
+        };
+        stored.as_ref() == Some(&sig.mime)
+    }
+}
+
+const ZIP: &[u8] = b"PK\x03\x04";
+const OLE: &[u8] = b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1";
+const EBML: &[u8] = b"\x1A\x45\xDF\xA3";
+const CAFEBABE: &[u8] = b"\xCA\xFE\xBA\xBE";
+
+const fn sig(offset: usize, magic: &'static [u8], mime: MimeType) -> Signature {
+    Signature {
+        offset,
+        magic,
+        refine: None,
+        window: 0,
+        mime,
+    }
+}
+
+const fn refined(
+    offset: usize,
+    magic: &'static [u8],
+    window: usize,
+    refine: fn(&[u8]) -> bool,
+    mime: MimeType,
+) -> Signature {
+    Signature {
+        offset,
+        magic,
+        refine: Some(Refine::Bytes(refine)),
+        window,
+        mime,
+    }
+}
+
+/// A signature for a format stored in a ZIP or OLE container, told apart by
+/// the container's entries.
+const fn stored(magic: &'static [u8], refine: Refine, mime: MimeType) -> Signature {
+    Signature {
+        offset: 0,
+        magic,
+        refine: Some(refine),
+        window: HEADER_LEN,
+        mime,
+    }
+}
+
+/// Signatures in priority order; the first match wins.
+///
+/// More specific signatures (e.g. ZIP-based documents) must come before the
+/// generic container signature they share a prefix with.
+const SIGNATURES: &[Signature] = &[
+    // Images
+    sig(0, b"\x89PNG\r\n\x1a\n", MimeType::Image(Image::Png)),
+    sig(0, b"\xFF\xD8\xFF", MimeType::Image(Image::Jpeg)),
+    sig(0, b"GIF87a", MimeType::Image(Image::Gif)),
+    sig(0, b"GIF89a", MimeType::Image(Image::Gif)),
+    refined(0, b"RIFF", 12, is_riff_webp, MimeType::Image(Image::Webp)),
+    refined(0, b"II*\x00", 10, is_cr2, MimeType::Image(Image::Cr2)),
+    sig(0, b"II*\x00", MimeType::Image(Image::Tif)),
+    sig(0, b"MM\x00*", MimeType::Image(Image::Tif)),
+    refined(
+        4,
+        b"ftyp",
+        HEADER_LEN,
+        is_ftyp_avif,
+        MimeType::Image(Image::Avif),
+    ),
+    refined(
+        4,
+        b"ftyp",
+        HEADER_LEN,
+        is_ftyp_heif,
+        MimeType::Image(Image::Heif),
+    ),
+    sig(0, b"II\xBC", MimeType::Image(Image::Jxr)),
+    sig(0, b"8BPS", MimeType::Image(Image::Psd)),
+    refined(0, b"BM", 18, is_bmp, MimeType::Image(Image::Bmp)),
+    refined(
+        0,
+        b"\x00\x00\x01\x00",
+        6,
+        is_ico,
+        MimeType::Image(Image::Ico),
+    ),
+    refined(0, b"AT&TFORM", 15, is_djvu, MimeType::Image(Image::Djvu)),
+    stored(ZIP, Refine::Zip, MimeType::Image(Image::Ora)),
+    refined(0, b"RIFF", 12, is_riff_ani, MimeType::Image(Image::Ani)),
+    // Video
+    refined(
+        4,
+        b"ftyp",
+        HEADER_LEN,
+        is_ftyp_m4v,
+        MimeType::Video(Video::M4v),
+    ),
+    refined(
+        4,
+        b"ftyp",
+        HEADER_LEN,
+        is_ftyp_qt,
+        MimeType::Video(Video::Mov),
+    ),
+    refined(
+        4,
+        b"ftyp",
+        HEADER_LEN,
+        is_ftyp_m4a,
+        MimeType::Audio(Audio::M4a),
+    ),
+    refined(
+        4,
+        b"ftyp",
+        HEADER_LEN,
+        is_ftyp_3gp,
+        MimeType::Video(Video::ThreeGp),
+    ),
+    sig(4, b"ftyp", MimeType::Video(Video::Mp4)),
+    sig(4, b"moov", MimeType::Video(Video::Mov)),
+    refined(0, EBML, HEADER_LEN, is_webm, MimeType::Video(Video::Webm)),
+    refined(0, EBML, HEADER_LEN, is_mk3d, MimeType::Video(Video::Mk3d)),
+    refined(0, EBML, HEADER_LEN, is_mka, MimeType::Audio(Audio::Mka)),
+    sig(0, EBML, MimeType::Video(Video::Mkv)),
+    refined(0, b"RIFF", 12, is_riff_avi, MimeType::Video(Video::Avi)),
+    sig(
+        0,
+        b"\x30\x26\xB2\x75\x8E\x66\xCF\x11\xA6\xD9\x00\xAA\x00\x62\xCE\x6C",
+        MimeType::Video(Video::Wmv),
+    ),
+    sig(0, b"\x00\x00\x01\xBA", MimeType::Video(Video::Mpg)),
+    sig(0, b"\x00\x00\x01\xB3", MimeType::Video(Video::Mpg)),
+    sig(0, b"FLV\x01", MimeType::Video(Video::Flv)),
+    // Audio
+    sig(0, b"MThd", MimeType::Audio(Audio::Midi)),
+    sig(0, b"ID3", MimeType::Audio(Audio::Mpeg)),
+    sig(0, b"\xFF\xFB", MimeType::Audio(Audio::Mpeg)),
+    sig(0, b"\xFF\xF3", MimeType::Audio(Audio::Mpeg)),
+    sig(0, b"\xFF\xF2", MimeType::Audio(Audio::Mpeg)),
+    refined(0, b"OggS", HEADER_LEN, is_ogv, MimeType::Video(Video::Ogv)),
+    refined(
+        0,
+        b"OggS",
+        HEADER_LEN,
+        is_opus,
+        MimeType::Audio(Audio::Opus),
+    ),
+    sig(0, b"OggS", MimeType::Audio(Audio::Ogg)),
+    sig(0, b"fLaC", MimeType::Audio(Audio::Flac)),
+    refined(0, b"RIFF", 12, is_riff_wave, MimeType::Audio(Audio::Wav)),
+    refined(0, b"RF64", 12, is_riff_wave, MimeType::Audio(Audio::Wav)),
+    refined(0, b"BW64", 12, is_riff_wave, MimeType::Audio(Audio::Wav)),
+    refined(0, b"RIFF", 12, is_riff_midi, MimeType::Audio(Audio::Midi)),
+    sig(0, b"#!AMR", MimeType::Audio(Audio::Amr)),
+    sig(0, b"\xFF\xF1", MimeType::Audio(Audio::Aac)),
+    sig(0, b"\xFF\xF9", MimeType::Audio(Audio::Aac)),
+    refined(0, b"FORM", 12, is_aiff, MimeType::Audio(Audio::Aiff)),
+    sig(0, b"DSD ", MimeType::Audio(Audio::Dsf)),
+    sig(0, b"MAC ", MimeType::Audio(Audio::Ape)),
+    // ZIP-based formats, before the generic ZIP signature
+    stored(ZIP, Refine::Zip, MimeType::Archive(Archive::Epub)),
+    stored(ZIP, Refine::Zip, MimeType::Document(Document::Odt)),
+    stored(ZIP, Refine::Zip, MimeType::Document(Document::Ods)),
+    stored(ZIP, Refine::Zip, MimeType::Document(Document::Odp)),
+    stored(ZIP, Refine::Zip, MimeType::Document(Document::Docx)),
+    stored(ZIP, Refine::Zip, MimeType::Document(Document::Xlsx)),
+    stored(ZIP, Refine::Zip, MimeType::Document(Document::Pptx)),
+    stored(ZIP, Refine::Zip, MimeType::Document(Document::Xps)),
+    stored(ZIP, Refine::Zip, MimeType::Archive(Archive::Apk)),
+    stored(ZIP, Refine::Zip, MimeType::Archive(Archive::Jar)),
+    // OLE compound documents, before the generic MSI signature
+    stored(OLE, Refine::Ole, MimeType::Document(Document::Doc)),
+    stored(OLE, Refine::Ole, MimeType::Document(Document::Xls)),
+    stored(OLE, Refine::Ole, MimeType::Document(Document::Ppt)),
+    // Archives
+    sig(0, ZIP, MimeType::Archive(Archive::Zip)),
+    sig(0, b"PK\x05\x06", MimeType::Archive(Archive::Zip)),
+    sig(0, b"PK\x07\x08", MimeType::Archive(Archive::Zip)),
+    sig(257, b"ustar", MimeType::Archive(Archive::Tar)),
+    sig(0, b"Rar!\x1A\x07", MimeType::Archive(Archive::Rar)),
+    sig(0, b"\x1F\x8B", MimeType::Archive(Archive::Gz)),
+    sig(0, b"BZh", MimeType::Archive(Archive::Bz2)),
+    sig(0, b"BZ3v1", MimeType::Archive(Archive::Bz3)),
+    sig(0, b"7z\xBC\xAF\x27\x1C", MimeType::Archive(Archive::SevenZ)),
+    sig(0, b"\xFD7zXZ\x00", MimeType::Archive(Archive::Xz)),
+    sig(0, b"%PDF", MimeType::Archive(Archive::Pdf)),
+    sig(0, b"FWS", MimeType::Archive(Archive::Swf)),
+    sig(0, b"CWS", MimeType::Archive(Archive::Swf)),
+    sig(0, b"ZWS", MimeType::Archive(Archive::Swf)),
+    sig(0, b"{\\rtf", MimeType::Archive(Archive::Rtf)),
+    refined(34, b"LP", 11, is_eot, MimeType::Archive(Archive::Eot)),
+    sig(0, b"%!PS", MimeType::Archive(Archive::Ps)),
+    sig(
+        0,
+        b"SQLite format 3\x00",
+        MimeType::Archive(Archive::Sqlite),
+    ),
+    sig(0, b"NES\x1A", MimeType::Archive(Archive::Nes)),
+    sig(0, b"Cr24", MimeType::Archive(Archive::Crx)),
+    sig(0, b"MSCF", MimeType::Archive(Archive::Cab)),
+    sig(0, b"ISc(", MimeType::Archive(Archive::Cab)),
+    sig(
+        0,
+        b"!<arch>\ndebian-binary",
+        MimeType::Archive(Archive::Deb),
+    ),
+    sig(0, b"!<arch>\n", MimeType::Archive(Archive::Ar)),
+    sig(0, b"\x1F\x9D", MimeType::Archive(Archive::Z)),
+    sig(0, b"\x1F\xA0", MimeType::Archive(Archive::Z)),
+    sig(0, b"LZIP", MimeType::Archive(Archive::Lz)),
+    sig(0, b"\xED\xAB\xEE\xDB", MimeType::Archive(Archive::Rpm)),
+    sig(128, b"DICM", MimeType::Archive(Archive::Dcm)),
+    sig(0, b"\x28\xB5\x2F\xFD", MimeType::Archive(Archive::Zst)),
+    sig(0, b"\x04\x22\x4D\x18", MimeType::Archive(Archive::Lz4)),
+    sig(0, OLE, MimeType::Archive(Archive::Msi)),
+    sig(0, b"070707", MimeType::Archive(Archive::Cpio)),
+    sig(0, b"070701", MimeType::Archive(Archive::Cpio)),
+    sig(0, b"070702", MimeType::Archive(Archive::Cpio)),
+    sig(0, b"\xC7\x71", MimeType::Archive(Archive::Cpio)),
+    sig(0, b"\x71\xC7", MimeType::Archive(Archive::Cpio)),
+    sig(0, b"PAR2\x00PKT", MimeType::Archive(Archive::Par2)),
+    // Books
+    sig(60, b"BOOKMOBI", MimeType::Book(Book::Mobi)),
+    // Fonts
+    sig(0, b"\x00\x01\x00\x00\x00", MimeType::Font(Font::Ttf)),
+    sig(0, b"true", MimeType::Font(Font::Ttf)),
+    sig(0, b"OTTO", MimeType::Font(Font::Otf)),
+    sig(0, b"wOFF", MimeType::Font(Font::Woff)),
+    sig(0, b"wOF2", MimeType::Font(Font::Woff2)),
+    // Applications
+    sig(0, b"\x00asm", MimeType::Application(Application::Wasm)),
+    refined(
+        0,
+        b"MZ",
+        HEADER_LEN,
+        is_pe_dll,
+        MimeType::Application(Application::Dll),
+    ),
+    sig(0, b"MZ", MimeType::Application(Application::Exe)),
+    refined(
+        0,
+        b"\x7FELF",
+        18,
+        is_elf_rel,
+        MimeType::Application(Application::Obj),
+    ),
+    sig(0, b"\x7FELF", MimeType::Application(Application::Elf)),
+    sig(0, b"BC\xC0\xDE", MimeType::Application(Application::Bc)),
+    sig(
+        0,
+        b"\xDE\xC0\x17\x0B",
+        MimeType::Application(Application::Bc),
+    ),
+    sig(
+        0,
+        b"\xFE\xED\xFA\xCE",
+        MimeType::Application(Application::Mach),
+    ),
+    sig(
+        0,
+        b"\xFE\xED\xFA\xCF",
+        MimeType::Application(Application::Mach),
+    ),
+    sig(
+        0,
+        b"\xCE\xFA\xED\xFE",
+        MimeType::Application(Application::Mach),
+    ),
+    sig(
+        0,
+        b"\xCF\xFA\xED\xFE",
+        MimeType::Application(Application::Mach),
+    ),
+    refined(
+        0,
+        CAFEBABE,
+        8,
+        is_mach_fat,
+        MimeType::Application(Application::Mach),
+    ),
+    sig(0, CAFEBABE, MimeType::Application(Application::Class)),
+    sig(0, b"dex\n", MimeType::Application(Application::Dex)),
+    sig(0, b"dey\n", MimeType::Application(Application::Dey)),
+    refined(
+        0,
+        b"\x30\x82",
+        6,
+        is_der_cert,
+        MimeType::Application(Application::Der),
+    ),
+    // Text
+    sig(0, b"<?xml", MimeType::Text(Text::Xml)),
+    refined(0, b"<", 14, is_html, MimeType::Text(Text::Html)),
+    sig(0, b"BEGIN:VCALENDAR", MimeType::Text(Text::Calendar)),
+    sig(0, b"BEGIN:VCARD", MimeType::Text(Text::Vcard)),
+    sig(0, b"WEBVTT", MimeType::Text(Text::Vtt)),
+];
+
+/// Number of leading bytes needed to evaluate every signature.
+pub(crate) const HEADER_LEN: usize = 8192;
+
+/// Detects a MIME type from the leading bytes of a file.
+///
+/// Only the first [`HEADER_LEN`] bytes are examined.
+pub(crate) fn detect(bytes: &[u8]) -> Option<MimeType> {
+    let header = Header::new(bytes);
+    SIGNATURES
+        .iter()
+        .find(|sig| {
+            header.bytes.get(sig.offset..sig.offset + sig.magic.len()) == Some(sig.magic)
+                && header.refine(sig)
+        })
+        .map(|sig| sig.mime.clone())
+}
+
+/// Detects a MIME type from a prefix of a file, or reports how many bytes
+/// are needed before the signatures can decide.
+///
+/// With `complete` set, `bytes` is the whole file and the result is the same
+/// as [`detect`]. Otherwise a signature that may still match once more bytes
+/// are known defers the decision, so the result never differs from the one
+/// [`detect`] gives for the first [`HEADER_LEN`] bytes of the file.
+pub(crate) fn detect_prefix(bytes: &[u8], complete: bool) -> Detection {
+    let header = Header::new(bytes);
+    let bytes = header.bytes;
+    for sig in SIGNATURES {
+        let end = sig.offset + sig.magic.len();
+        // A mismatch in the part of the magic already received is final
+        let known = bytes
+            .get(sig.offset..bytes.len().min(end))
+            .unwrap_or_default();
+        if known != &sig.magic[..known.len()] {
+            continue;
+        }
+        if bytes.len() < end {
+            if complete {
+                continue;
+            }
+            return Detection::NeedMore(end);
+        }
+        if sig.refine.is_none() {
+            return Detection::Detected(sig.mime.clone());
+        }
+        if !complete && bytes.len() < sig.window {
+            return Detection::NeedMore(sig.window);
+        }
+        if header.refine(sig) {
+            return Detection::Detected(sig.mime.clone());
+        }
+    }
+    Detection::Unknown
+}
+
+/// Returns `true` if `candidate` is a more specific format stored in the
+/// generic container `detected`.
+///
+/// Content detection falls back to the container type (ZIP, OLE, ISO base
+/// media) when the specific format cannot be told apart from the header, in
+/// which case the file extension is the better hint.
+pub(crate) fn is_refinement(detected: &MimeType, candidate: &MimeType) -> bool {
+    match detected {
+        MimeType::Archive(Archive::Zip) => matches!(
+            candidate,
+            MimeType::Archive(Archive::Epub)
+                | MimeType::Book(Book::Epub)
+                | MimeType::Document(Document::Docx)
+                | MimeType::Document(Document::Xlsx)
+                | MimeType::Document(Document::Pptx)
+                | MimeType::Document(Document::Odt)
+                | MimeType::Document(Document::Ods)
+                | MimeType::Document(Document::Odp)
+                | MimeType::Document(Document::Xps)
+                | MimeType::Archive(Archive::Jar)
+                | MimeType::Archive(Archive::Apk)
+                | MimeType::Image(Image::Ora)
+        ),
+        MimeType::Archive(Archive::Msi) => matches!(
+            candidate,
+            MimeType::Document(Document::Doc)
+                | MimeType::Document(Document::Xls)
+                | MimeType::Document(Document::Ppt)
+        ),
+        MimeType::Archive(Archive::Epub) => candidate == &MimeType::Book(Book::Epub),
+        MimeType::Audio(Audio::Ogg) => matches!(
+            candidate,
+            MimeType::Audio(Audio::Opus) | MimeType::Video(Video::Ogv)
+        ),
+        MimeType::Video(Video::Mkv) => matches!(
+            candidate,
+            MimeType::Audio(Audio::Mka) | MimeType::Video(Video::Mk3d)
+        ),
+        MimeType::Video(Video::Mp4) => matches!(
+            candidate,
+            MimeType::Video(Video::M4v)
+                | MimeType::Video(Video::Mov)
+                | MimeType::Audio(Audio::M4a)
+                | MimeType::Video(Video::ThreeGp)
+                | MimeType::Image(Image::Heif)
+                | MimeType::Image(Image::Avif)
+        ),
+        _ => false,
+    }
+}
+
+fn u16_le(bytes: &[u8], offset: usize) -> Option<u16> {
+    let b = bytes.get(offset..offset + 2)?;
+    Some(u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn u32_le(bytes: &[u8], offset: usize) -> Option<u32> {
+    let b = bytes.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn u32_be(bytes: &[u8], offset: usize) -> Option<u32> {
+    let b = bytes.get(offset..offset + 4)?;
+    Some(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+/// Classifies a RIFF file by its form type.
+fn riff_type(bytes: &[u8]) -> Option<MimeType> {
+    Riff::parse(bytes).and_then(|riff| riff.mime_type())
+}
+
+fn is_riff_webp(bytes: &[u8]) -> bool {
+    riff_type(bytes) == Some(MimeType::Image(Image::Webp))
+}
+
+fn is_riff_ani(bytes: &[u8]) -> bool {
+    riff_type(bytes) == Some(MimeType::Image(Image::Ani))
+}
+
+fn is_riff_avi(bytes: &[u8]) -> bool {
+    riff_type(bytes) == Some(MimeType::Video(Video::Avi))
+}
+
+fn is_riff_wave(bytes: &[u8]) -> bool {
+    riff_type(bytes) == Some(MimeType::Audio(Audio::Wav))
+}
+
+fn is_riff_midi(bytes: &[u8]) -> bool {
+    riff_type(bytes) == Some(MimeType::Audio(Audio::Midi))
+}
+
+/// Classifies an Ogg file by the codecs of its streams.
+fn ogg_type(bytes: &[u8]) -> Option<MimeType> {
+    Ogg::parse(bytes).map(|ogg| ogg.mime_type())
+}
+
+fn is_ogv(bytes: &[u8]) -> bool {
+    ogg_type(bytes) == Some(MimeType::Video(Video::Ogv))
+}
+
+fn is_opus(bytes: &[u8]) -> bool {
+    ogg_type(bytes) == Some(MimeType::Audio(Audio::Opus))
+}
+
+fn is_aiff(bytes: &[u8]) -> bool {
+    matches!(bytes.get(8..12), Some(b"AIFF") | Some(b"AIFC"))
+}
+
+fn is_cr2(bytes: &[u8]) -> bool {
+    bytes.get(8..10) == Some(b"CR")
+}
+
+/// Checks the size of the DIB header, which tells its version apart.
+fn is_bmp(bytes: &[u8]) -> bool {
+    u32_le(bytes, 14).is_some_and(|size| matches!(size, 12 | 40 | 52 | 56 | 108 | 124))
+}
+
+fn is_ico(bytes: &[u8]) -> bool {
+    u16_le(bytes, 4).is_some_and(|count| count > 0)
+}
+
+fn is_djvu(bytes: &[u8]) -> bool {
+    bytes.get(12..15) == Some(b"DJV")
+}
+
+/// Classifies an ISO base media file by its brands and track handlers.
+fn ftyp_type(bytes: &[u8]) -> Option<MimeType> {
+    IsoBmff::parse(bytes).map(|bmff| bmff.mime_type())
+}
+
+fn is_ftyp_avif(bytes: &[u8]) -> bool {
+    ftyp_type(bytes) == Some(MimeType::Image(Image::Avif))
+}
+
+fn is_ftyp_heif(bytes: &[u8]) -> bool {
+    ftyp_type(bytes) == Some(MimeType::Image(Image::Heif))
+}
+
+fn is_ftyp_m4v(bytes: &[u8]) -> bool {
+    ftyp_type(bytes) == Some(MimeType::Video(Video::M4v))
+}
+
+fn is_ftyp_m4a(bytes: &[u8]) -> bool {
+    ftyp_type(bytes) == Some(MimeType::Audio(Audio::M4a))
+}
+
+fn is_ftyp_qt(bytes: &[u8]) -> bool {
+    ftyp_type(bytes) == Some(MimeType::Video(Video::Mov))
+}
+
+fn is_ftyp_3gp(bytes: &[u8]) -> bool {
+    ftyp_type(bytes) == Some(MimeType::Video(Video::ThreeGp))
+}
+
+/// Classifies a Matroska or WebM file by its DocType and tracks.
+fn ebml_type(bytes: &[u8]) -> Option<MimeType> {
+    Ebml::parse(bytes).and_then(|ebml| ebml.mime_type())
+}
+
+fn is_webm(bytes: &[u8]) -> bool {
+    ebml_type(bytes) == Some(MimeType::Video(Video::Webm))
+}
+
+fn is_mk3d(bytes: &[u8]) -> bool {
+    ebml_type(bytes) == Some(MimeType::Video(Video::Mk3d))
+}
+
+fn is_mka(bytes: &[u8]) -> bool {
+    ebml_type(bytes) == Some(MimeType::Audio(Audio::Mka))
+}
+
+fn is_eot(bytes: &[u8]) -> bool {
+    matches!(
+        bytes.get(8..11),
+        Some(b"\x00\x00\x01") | Some(b"\x01\x00\x02") | Some(b"\x02\x00\x02")
+    )
+}
+
+/// Identifies the format stored in a ZIP file from its entries.
+fn zip_type(bytes: &[u8]) -> Option<MimeType> {
+    Zip::parse(bytes).map(|zip| zip.mime_type())
+}
+
+/// Identifies the document stored in an OLE compound file by the names of
+/// its streams, which the directory stores in UTF-16LE.
+fn ole_type(bytes: &[u8]) -> Option<MimeType> {
+    let has_stream = |name: &str| {
+        let encoded: Vec<u8> = name.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        contains(bytes, &encoded)
+    };
+    let mime = if has_stream("WordDocument") {
+        MimeType::Document(Document::Doc)
+    } else if has_stream("Workbook") || has_stream("Book") {
+        MimeType::Document(Document::Xls)
+    } else if has_stream("PowerPoint Document") {
+        MimeType::Document(Document::Ppt)
+    } else {
+        return None;
+    };
+    Some(mime)
+}
+
+/// Checks the `IMAGE_FILE_DLL` flag in the PE file header.
+fn is_pe_dll(bytes: &[u8]) -> bool {
+    let Some(pe_offset) = u32_le(bytes, 0x3C) else {
+        return false;
+    };
+    let pe_offset = pe_offset as usize;
+    bytes.get(pe_offset..pe_offset + 4) == Some(b"PE\x00\x00")
+        && u16_le(bytes, pe_offset + 22).is_some_and(|flags| flags & 0x2000 != 0)
+}
+
+/// Checks for an ELF relocatable object (`ET_REL`).
+fn is_elf_rel(bytes: &[u8]) -> bool {
+    let e_type = match bytes.get(5) {
+        Some(1) => u16_le(bytes, 16),
+        Some(2) => bytes.get(16..18).map(|b| u16::from_be_bytes([b[0], b[1]])),
+        _ => None,
+    };
+    e_type == Some(1)
+}
+
+/// Tells a Mach-O universal binary apart from a Java class file.
+///
+/// Both start with `CAFEBABE`; a fat binary follows it with a small
+/// architecture count while a class file has its version (>= 45) there.
+fn is_mach_fat(bytes: &[u8]) -> bool {
+    u32_be(bytes, 4).is_some_and(|count| count > 0 && count < 20)
+}
+
+/// Checks for a DER `SEQUENCE` wrapping another `SEQUENCE`, as in X.509.
+fn is_der_cert(bytes: &[u8]) -> bool {
+    bytes.get(4..6) == Some(b"\x30\x82")
+}
+
+/// Checks for an HTML doctype or root element, ignoring ASCII case.
+fn is_html(bytes: &[u8]) -> bool {
+    [b"<!doctype html".as_slice(), b"<html"]
+        .iter()
+        .any(|prefix| {
+            bytes
+                .get(..prefix.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+        })
+}
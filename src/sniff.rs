@@ -0,0 +1,562 @@
+use crate::{MediaType, MimeFormat, MimeType};
+
+/// Maximum number of bytes the sniffing algorithms look at.
+const RESOURCE_HEADER_LEN: usize = 1445;
+
+/// Bytes skipped before HTML and XML patterns.
+const WHITESPACE: &[u8] = b"\t\n\x0c\r ";
+
+/// `Content-Type` values sent by Apache for unknown files, which may label
+/// binary content as text.
+const APACHE_BUG_TYPES: &[&str] = &[
+    "text/plain",
+    "text/plain; charset=ISO-8859-1",
+    "text/plain; charset=iso-8859-1",
+    "text/plain; charset=UTF-8",
+];
+
+/// The context a resource is used in, which selects the sniffing rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SniffContext {
+    /// A document loaded in a browsing context, using the full MIME type
+    /// sniffing algorithm.
+    Browsing,
+    /// An `<img>` or CSS image.
+    Image,
+    /// An `<audio>` or `<video>` source.
+    AudioVideo,
+    /// A web font.
+    Font,
+    /// Only distinguishes text from binary content.
+    TextOrBinary,
+}
+
+/// Browser-compatible content type detection following the
+/// [WHATWG MIME Sniffing Standard](https://mimesniff.spec.whatwg.org/).
+///
+/// Unlike [`MimeType::from_bytes`], this follows the exact rules browsers use:
+/// it only looks at the first 1445 bytes, honours the supplied
+/// `Content-Type` and `X-Content-Type-Options: nosniff`, and never sniffs
+/// scriptable types such as HTML from content labelled as something else.
+/// Types the standard names resolve to the crate's variants where one exists,
+/// e.g. `image/x-icon` to `Image::Ico`; the rest, such as
+/// `application/octet-stream`, become [`MimeType::Other`].
+///
+/// # Examples
+///
+/// ```
+/// use mime_type::{Image, MimeSniffer, MimeType, SniffContext, Text};
+///
+/// let png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR";
+///
+/// // No Content-Type: sniff the content
+/// let sniffer = MimeSniffer::new();
+/// assert_eq!(
+///     sniffer.sniff(png, SniffContext::Browsing),
+///     Some(MimeType::Image(Image::Png))
+/// );
+///
+/// // A mislabeled image is corrected, but HTML is never sniffed from text
+/// let sniffer = MimeSniffer::new().supplied_type("image/gif");
+/// assert_eq!(sniffer.sniff(png, SniffContext::Browsing), Some(MimeType::Image(Image::Png)));
+/// let sniffer = MimeSniffer::new().supplied_type("text/plain; charset=utf-8");
+/// assert_eq!(
+///     sniffer.sniff(b"<html><script>", SniffContext::Browsing),
+///     Some(MimeType::Text(Text::Plain))
+/// );
+///
+/// // nosniff keeps the supplied type
+/// let sniffer = MimeSniffer::new()
+///     .supplied_type("image/gif")
+///     .content_type_options("nosniff");
+/// assert_eq!(sniffer.sniff(png, SniffContext::Browsing), Some(MimeType::Image(Image::Gif)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MimeSniffer {
+    supplied: Option<String>,
+    no_sniff: bool,
+}
+
+impl MimeSniffer {
+    /// Creates a sniffer without a supplied type.
+    pub fn new() -> MimeSniffer {
+        MimeSniffer::default()
+    }
+
+    /// Sets the supplied type, i.e. the raw `Content-Type` header value.
+    ///
+    /// Invalid values count as no supplied type.
+    pub fn supplied_type(mut self, content_type: &str) -> MimeSniffer {
+        self.supplied = Some(content_type.to_string());
+        self
+    }
+
+    /// Sets the no-sniff flag directly.
+    pub fn no_sniff(mut self, no_sniff: bool) -> MimeSniffer {
+        self.no_sniff = no_sniff;
+        self
+    }
+
+    /// Sets the no-sniff flag from an `X-Content-Type-Options` header value.
+    ///
+    /// As in the Fetch standard, only the first comma-separated value is
+    /// considered, matched case-insensitively against `nosniff`.
+    pub fn content_type_options(self, value: &str) -> MimeSniffer {
+        let first = value.split(',').next().unwrap_or_default();
+        let no_sniff = first
+            .trim_matches([' ', '\t'])
+            .eq_ignore_ascii_case("nosniff");
+        self.no_sniff(no_sniff)
+    }
+
+    /// Determines the computed MIME type of a resource in a context.
+    ///
+    /// Returns `None` only when there is no supplied type and the context's
+    /// rules do not recognize the content.
+    pub fn sniff(&self, bytes: &[u8], context: SniffContext) -> Option<MimeType> {
+        let header = &bytes[..bytes.len().min(RESOURCE_HEADER_LEN)];
+        let supplied = self
+            .supplied
+            .as_deref()
+            .and_then(|supplied| MediaType::parse(supplied).ok());
+        match context {
+            SniffContext::Browsing => Some(self.browsing(header, supplied)),
+            SniffContext::Image => specific(header, supplied, match_image),
+            SniffContext::AudioVideo => specific(header, supplied, match_audio_video),
+            SniffContext::Font => specific(header, supplied, match_font),
+            SniffContext::TextOrBinary => Some(mime(text_or_binary(header))),
+        }
+    }
+
+    /// The MIME type sniffing algorithm for browsing contexts.
+    fn browsing(&self, header: &[u8], supplied: Option<MediaType>) -> MimeType {
+        let supplied = match supplied {
+            Some(supplied)
+                if !matches!(
+                    supplied.essence().as_str(),
+                    "unknown/unknown" | "application/unknown" | "*/*"
+                ) =>
+            {
+                supplied
+            }
+            _ => return mime(identify_unknown(header, !self.no_sniff)),
+        };
+        if self.no_sniff {
+            return MimeType::from(supplied);
+        }
+        let apache_bug = self
+            .supplied
+            .as_deref()
+            .is_some_and(|raw| APACHE_BUG_TYPES.contains(&raw));
+        if apache_bug {
+            return mime(text_or_binary(header));
+        }
+        if is_xml(&supplied) {
+            return MimeType::from(supplied);
+        }
+        if supplied.essence() == "text/html" {
+            return match sniff_feed(header) {
+                Some(feed) => mime(feed),
+                None => MimeType::from(supplied),
+            };
+        }
+        let matched = match supplied.type_() {
+            "image" => match_image(header),
+            "audio" | "video" => match_audio_video(header),
+            _ if supplied.essence() == "application/ogg" => match_audio_video(header),
+            _ => None,
+        };
+        matched.map_or_else(|| MimeType::from(supplied), mime)
+    }
+}
+
+/// The image, audio/video and font context rules: an XML supplied type is
+/// kept, otherwise a matching pattern wins over the supplied type.
+fn specific(
+    header: &[u8],
+    supplied: Option<MediaType>,
+    matcher: fn(&[u8]) -> Option<&'static str>,
+) -> Option<MimeType> {
+    if let Some(supplied) = &supplied
+        && is_xml(supplied)
+    {
+        return Some(MimeType::from(supplied.clone()));
+    }
+    matcher(header).map(mime).or(supplied.map(MimeType::from))
+}
+
+/// Resolves a MIME string named by the standard.
+fn mime(essence: &'static str) -> MimeType {
+    MimeType::from_mime(essence).expect("the standard only names valid MIME types")
+}
+
+fn is_xml(media: &MediaType) -> bool {
+    media.subtype().ends_with("+xml")
+        || matches!(media.essence().as_str(), "text/xml" | "application/xml")
+}
+
+/// A byte pattern from the standard's tables.
+struct Pattern {
+    pattern: &'static [u8],
+    mask: &'static [u8],
+    /// Whether leading whitespace is skipped before matching.
+    skip_whitespace: bool,
+    /// Whether the pattern must be followed by a space or `>`.
+    tag: bool,
+    mime: &'static str,
+}
+
+impl Pattern {
+    const fn new(pattern: &'static [u8], mask: &'static [u8], mime: &'static str) -> Pattern {
+        Pattern {
+            pattern,
+            mask,
+            skip_whitespace: false,
+            tag: false,
+            mime,
+        }
+    }
+
+    /// An HTML tag pattern, matched case-insensitively after whitespace.
+    const fn html(pattern: &'static [u8], mask: &'static [u8]) -> Pattern {
+        Pattern {
+            pattern,
+            mask,
+            skip_whitespace: true,
+            tag: true,
+            mime: "text/html",
+        }
+    }
+
+    fn matches(&self, input: &[u8]) -> bool {
+        let start = if self.skip_whitespace {
+            input
+                .iter()
+                .position(|b| !WHITESPACE.contains(b))
+                .unwrap_or(input.len())
+        } else {
+            0
+        };
+        let Some(window) = input.get(start..start + self.pattern.len()) else {
+            return false;
+        };
+        let matched = window
+            .iter()
+            .zip(self.mask)
+            .zip(self.pattern)
+            .all(|((byte, mask), pattern)| byte & mask == *pattern);
+        matched && (!self.tag || matches!(input.get(start + self.pattern.len()), Some(b' ' | b'>')))
+    }
+}
+
+/// Returns the type of the first matching pattern.
+fn match_patterns(patterns: &[Pattern], input: &[u8]) -> Option<&'static str> {
+    patterns
+        .iter()
+        .find(|pattern| pattern.matches(input))
+        .map(|pattern| pattern.mime)
+}
+
+const FF2: &[u8] = &[0xFF; 2];
+const FF3: &[u8] = &[0xFF; 3];
+const FF4: &[u8] = &[0xFF; 4];
+const FF5: &[u8] = &[0xFF; 5];
+const FF6: &[u8] = &[0xFF; 6];
+const FF8: &[u8] = &[0xFF; 8];
+
+/// Patterns of scriptable types, only used when sniffing them is allowed.
+const SCRIPTABLE_PATTERNS: &[Pattern] = &[
+    Pattern::html(
+        b"<!DOCTYPE HTML",
+        b"\xFF\xFF\xDF\xDF\xDF\xDF\xDF\xDF\xDF\xFF\xDF\xDF\xDF\xDF",
+    ),
+    Pattern::html(b"<HTML", b"\xFF\xDF\xDF\xDF\xDF"),
+    Pattern::html(b"<HEAD", b"\xFF\xDF\xDF\xDF\xDF"),
+    Pattern::html(b"<SCRIPT", b"\xFF\xDF\xDF\xDF\xDF\xDF\xDF"),
+    Pattern::html(b"<IFRAME", b"\xFF\xDF\xDF\xDF\xDF\xDF\xDF"),
+    Pattern::html(b"<H1", b"\xFF\xDF\xFF"),
+    Pattern::html(b"<DIV", b"\xFF\xDF\xDF\xDF"),
+    Pattern::html(b"<FONT", b"\xFF\xDF\xDF\xDF\xDF"),
+    Pattern::html(b"<TABLE", b"\xFF\xDF\xDF\xDF\xDF\xDF"),
+    Pattern::html(b"<A", b"\xFF\xDF"),
+    Pattern::html(b"<STYLE", b"\xFF\xDF\xDF\xDF\xDF\xDF"),
+    Pattern::html(b"<TITLE", b"\xFF\xDF\xDF\xDF\xDF\xDF"),
+    Pattern::html(b"<B", b"\xFF\xDF"),
+    Pattern::html(b"<BODY", b"\xFF\xDF\xDF\xDF\xDF"),
+    Pattern::html(b"<BR", b"\xFF\xDF\xDF"),
+    Pattern::html(b"<P", b"\xFF\xDF"),
+    Pattern::html(b"<!--", FF4),
+    Pattern {
+        skip_whitespace: true,
+        ..Pattern::new(b"<?xml", FF5, "text/xml")
+    },
+    Pattern::new(b"%PDF-", FF5, "application/pdf"),
+];
+
+/// Patterns checked after the scriptable ones.
+const TEXT_PATTERNS: &[Pattern] = &[
+    Pattern::new(b"%!PS-Adobe-", &[0xFF; 11], "application/postscript"),
+    Pattern::new(b"\xFE\xFF\0\0", b"\xFF\xFF\0\0", "text/plain"),
+    Pattern::new(b"\xFF\xFE\0\0", b"\xFF\xFF\0\0", "text/plain"),
+    Pattern::new(b"\xEF\xBB\xBF\0", b"\xFF\xFF\xFF\0", "text/plain"),
+];
+
+const IMAGE_PATTERNS: &[Pattern] = &[
+    Pattern::new(b"\0\0\x01\0", FF4, "image/x-icon"),
+    Pattern::new(b"\0\0\x02\0", FF4, "image/x-icon"),
+    Pattern::new(b"BM", FF2, "image/bmp"),
+    Pattern::new(b"GIF87a", FF6, "image/gif"),
+    Pattern::new(b"GIF89a", FF6, "image/gif"),
+    Pattern::new(
+        b"RIFF\0\0\0\0WEBPVP",
+        b"\xFF\xFF\xFF\xFF\0\0\0\0\xFF\xFF\xFF\xFF\xFF\xFF",
+        "image/webp",
+    ),
+    Pattern::new(b"\x89PNG\r\n\x1a\n", FF8, "image/png"),
+    Pattern::new(b"\xFF\xD8\xFF", FF3, "image/jpeg"),
+];
+
+const AUDIO_VIDEO_PATTERNS: &[Pattern] = &[
+    Pattern::new(b".snd", FF4, "audio/basic"),
+    Pattern::new(
+        b"FORM\0\0\0\0AIFF",
+        b"\xFF\xFF\xFF\xFF\0\0\0\0\xFF\xFF\xFF\xFF",
+        "audio/aiff",
+    ),
+    Pattern::new(b"ID3", FF3, "audio/mpeg"),
+    Pattern::new(b"OggS\0", FF5, "application/ogg"),
+    Pattern::new(b"MThd\0\0\0\x06", FF8, "audio/midi"),
+    Pattern::new(
+        b"RIFF\0\0\0\0AVI ",
+        b"\xFF\xFF\xFF\xFF\0\0\0\0\xFF\xFF\xFF\xFF",
+        "video/avi",
+    ),
+    Pattern::new(
+        b"RIFF\0\0\0\0WAVE",
+        b"\xFF\xFF\xFF\xFF\0\0\0\0\xFF\xFF\xFF\xFF",
+        "audio/wave",
+    ),
+];
+
+const FONT_PATTERNS: &[Pattern] = &[
+    Pattern::new(
+        b"\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0LP",
+        b"\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\xFF\xFF",
+        "application/vnd.ms-fontobject",
+    ),
+    Pattern::new(b"\0\x01\0\0", FF4, "font/ttf"),
+    Pattern::new(b"OTTO", FF4, "font/otf"),
+    Pattern::new(b"ttcf", FF4, "font/collection"),
+    Pattern::new(b"wOFF", FF4, "font/woff"),
+    Pattern::new(b"wOF2", FF4, "font/woff2"),
+];
+
+const ARCHIVE_PATTERNS: &[Pattern] = &[
+    Pattern::new(b"\x1F\x8B\x08", FF3, "application/x-gzip"),
+    Pattern::new(b"PK\x03\x04", FF4, "application/zip"),
+    Pattern::new(
+        b"Rar!\x1A\x07\0",
+        &[0xFF; 7],
+        "application/x-rar-compressed",
+    ),
+];
+
+/// The rules for identifying an unknown MIME type.
+fn identify_unknown(header: &[u8], sniff_scriptable: bool) -> &'static str {
+    if sniff_scriptable && let Some(mime) = match_patterns(SCRIPTABLE_PATTERNS, header) {
+        return mime;
+    }
+    match_patterns(TEXT_PATTERNS, header)
+        .or_else(|| match_image(header))
+        .or_else(|| match_audio_video(header))
+        .or_else(|| match_patterns(ARCHIVE_PATTERNS, header))
+        .unwrap_or_else(|| {
+            if header.iter().any(|&b| is_binary_byte(b)) {
+                "application/octet-stream"
+            } else {
+                "text/plain"
+            }
+        })
+}
+
+/// The rules for distinguishing if a resource is text or binary.
+fn text_or_binary(header: &[u8]) -> &'static str {
+    let bom = header.starts_with(b"\xFE\xFF")
+        || header.starts_with(b"\xFF\xFE")
+        || header.starts_with(b"\xEF\xBB\xBF");
+    if bom || !header.iter().any(|&b| is_binary_byte(b)) {
+        "text/plain"
+    } else {
+        "application/octet-stream"
+    }
+}
+
+fn is_binary_byte(byte: u8) -> bool {
+    matches!(byte, 0x00..=0x08 | 0x0B | 0x0E..=0x1A | 0x1C..=0x1F)
+}
+
+/// Image type pattern matching.
+fn match_image(header: &[u8]) -> Option<&'static str> {
+    match_patterns(IMAGE_PATTERNS, header)
+}
+
+/// Audio or video type pattern matching.
+fn match_audio_video(header: &[u8]) -> Option<&'static str> {
+    match_patterns(AUDIO_VIDEO_PATTERNS, header).or_else(|| {
+        if is_mp4(header) {
+            Some("video/mp4")
+        } else if is_webm(header) {
+            Some("video/webm")
+        } else if is_mp3_without_id3(header) {
+            Some("audio/mpeg")
+        } else {
+            None
+        }
+    })
+}
+
+/// Font type pattern matching.
+fn match_font(header: &[u8]) -> Option<&'static str> {
+    match_patterns(FONT_PATTERNS, header)
+}
+
+/// The signature for MP4: an `ftyp` box listing an `mp4` brand.
+fn is_mp4(header: &[u8]) -> bool {
+    if header.len() < 12 {
+        return false;
+    }
+    let box_size = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+    if header.len() < box_size || !box_size.is_multiple_of(4) || &header[4..8] != b"ftyp" {
+        return false;
+    }
+    if &header[8..11] == b"mp4" {
+        return true;
+    }
+    (16..box_size)
+        .step_by(4)
+        .any(|idx| header.get(idx..idx + 3) == Some(b"mp4"))
+}
+
+/// The signature for WebM: an EBML header with a `webm` DocType.
+fn is_webm(header: &[u8]) -> bool {
+    if !header.starts_with(b"\x1A\x45\xDF\xA3") {
+        return false;
+    }
+    let mut iter = 4;
+    while iter < header.len() && iter < 38 {
+        if header[iter..].starts_with(b"\x42\x82") {
+            iter += 2;
+            if iter >= header.len() {
+                break;
+            }
+            iter += vint_size(header[iter]);
+            if iter + 4 <= header.len() && matches_padded(header, iter, b"webm") {
+                return true;
+            }
+        }
+        iter += 1;
+    }
+    false
+}
+
+/// Returns the length of an EBML variable-size integer from its first byte.
+fn vint_size(first: u8) -> usize {
+    (first.leading_zeros() as usize + 1).min(8)
+}
+
+/// Matches a pattern after skipping `0x00` padding bytes.
+fn matches_padded(header: &[u8], offset: usize, pattern: &[u8]) -> bool {
+    let start = header[offset..]
+        .iter()
+        .position(|&b| b != 0)
+        .map_or(header.len(), |idx| offset + idx);
+    header[start..].starts_with(pattern)
+}
+
+/// The signature for MP3 without ID3: two consecutive MPEG audio frames.
+fn is_mp3_without_id3(header: &[u8]) -> bool {
+    let Some((version, bitrate, freq, padding)) = mp3_frame(header, 0) else {
+        return false;
+    };
+    let scale = if version == 1 { 72 } else { 144 };
+    let size = bitrate * scale / freq + padding;
+    if size < 4 || size > header.len() {
+        return false;
+    }
+    mp3_frame(header, size).is_some()
+}
+
+/// Parses an MPEG audio Layer III frame header, returning its version,
+/// bit rate, sample rate and padding.
+fn mp3_frame(header: &[u8], offset: usize) -> Option<(u8, usize, usize, usize)> {
+    const MP3_RATES: [usize; 15] = [
+        0, 32000, 40000, 48000, 56000, 64000, 80000, 96000, 112000, 128000, 160000, 192000, 224000,
+        256000, 320000,
+    ];
+    const MP25_RATES: [usize; 15] = [
+        0, 8000, 16000, 24000, 32000, 40000, 48000, 56000, 64000, 80000, 96000, 112000, 128000,
+        144000, 160000,
+    ];
+    const SAMPLE_RATES: [usize; 3] = [44100, 48000, 32000];
+
+    let frame = header.get(offset..offset + 4)?;
+    if frame[0] != 0xFF || frame[1] & 0xE0 != 0xE0 {
+        return None;
+    }
+    let layer = (frame[1] & 0x06) >> 1;
+    let bitrate_index = ((frame[2] & 0xF0) >> 4) as usize;
+    let sample_rate_index = ((frame[2] & 0x0C) >> 2) as usize;
+    if layer != 1 || bitrate_index == 15 || sample_rate_index == 3 {
+        return None;
+    }
+    let version = (frame[1] & 0x18) >> 3;
+    let bitrate = if version & 0x01 != 0 {
+        MP3_RATES[bitrate_index]
+    } else {
+        MP25_RATES[bitrate_index]
+    };
+    let padding = ((frame[2] & 0x02) >> 1) as usize;
+    Some((version, bitrate, SAMPLE_RATES[sample_rate_index], padding))
+}
+
+/// The rules for distinguishing if a resource is a feed or HTML.
+fn sniff_feed(header: &[u8]) -> Option<&'static str> {
+    let mut s = if header.starts_with(b"\xEF\xBB\xBF") {
+        3
+    } else {
+        0
+    };
+    loop {
+        while header.get(s).is_some_and(|b| WHITESPACE.contains(b)) {
+            s += 1;
+        }
+        if header.get(s) != Some(&b'<') {
+            return None;
+        }
+        s += 1;
+        let rest = &header[s..];
+        if let Some(comment) = rest.strip_prefix(b"!--") {
+            s += 3 + find(comment, b"-->")? + 3;
+        } else if rest.starts_with(b"!") {
+            s += find(rest, b">")? + 1;
+        } else if rest.starts_with(b"?") {
+            s += find(rest, b"?>")? + 2;
+        } else if rest.starts_with(b"rss") {
+            return Some("application/rss+xml");
+        } else if rest.starts_with(b"feed") {
+            return Some("application/atom+xml");
+        } else if let Some(rdf) = rest.strip_prefix(b"rdf:RDF") {
+            let is_rss = find(rdf, b"http://purl.org/rss/1.0/").is_some()
+                && find(rdf, b"http://www.w3.org/1999/02/22-rdf-syntax-ns#").is_some();
+            return is_rss.then_some("application/rss+xml");
+        } else {
+            return None;
+        }
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
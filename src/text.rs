@@ -0,0 +1,87 @@
+mime_category! {
+    /// Text file formats.
+    ///
+    /// Supports common web content, data interchange and plain text formats.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::Text;
+    ///
+    /// assert_eq!(Text::Html.to_string(), "text/html");
+    /// assert_eq!(Text::Html.extensions(), &["html", "htm"]);
+    /// ```
+    pub enum Text {
+        /// Plain text
+        Plain {
+            mime: "text/plain",
+            aliases: [],
+            extensions: ["txt", "text", "log"],
+        },
+        /// HTML document
+        Html {
+            mime: "text/html",
+            aliases: [],
+            extensions: ["html", "htm"],
+        },
+        /// Cascading Style Sheets
+        Css {
+            mime: "text/css",
+            aliases: [],
+            extensions: ["css"],
+        },
+        /// JavaScript source code
+        Javascript {
+            mime: "text/javascript",
+            aliases: [
+                "application/javascript",
+                "application/x-javascript",
+                "application/ecmascript",
+                "text/ecmascript",
+            ],
+            extensions: ["js", "mjs", "cjs"],
+        },
+        /// Comma-separated values
+        Csv {
+            mime: "text/csv",
+            aliases: [],
+            extensions: ["csv"],
+        },
+        /// Tab-separated values
+        Tsv {
+            mime: "text/tab-separated-values",
+            aliases: [],
+            extensions: ["tsv"],
+        },
+        /// Markdown document
+        Markdown {
+            mime: "text/markdown",
+            aliases: ["text/x-markdown"],
+            extensions: ["md", "markdown"],
+        },
+        /// XML document
+        Xml {
+            mime: "text/xml",
+            aliases: ["application/xml"],
+            extensions: ["xml"],
+        },
+        /// iCalendar data
+        Calendar {
+            mime: "text/calendar",
+            aliases: [],
+            extensions: ["ics"],
+        },
+        /// vCard contact data
+        Vcard {
+            mime: "text/vcard",
+            aliases: ["text/x-vcard"],
+            extensions: ["vcf", "vcard"],
+        },
+        /// WebVTT subtitles
+        Vtt {
+            mime: "text/vtt",
+            aliases: [],
+            extensions: ["vtt"],
+        },
+    }
+}
@@ -0,0 +1,460 @@
+use crate::regex::Regex;
+use crate::registry::{BUILTIN_PRIORITY, Glob, Sniffer};
+use crate::shared_mime_info::{MagicEntry, Matchlet, Pattern};
+use crate::xml::{Event, XmlReader};
+use crate::{MimeRegistry, MimeType};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
+
+/// Weight of Tika globs, which have none of their own.
+const GLOB_WEIGHT: u32 = 50;
+
+/// Priority of `root-XML` rules, above the default magic priority so that a
+/// root element match refines generic XML magic.
+const ROOT_XML_PRIORITY: u32 = BUILTIN_PRIORITY + 10;
+
+impl MimeRegistry {
+    /// Creates a registry from Apache Tika's `tika-mimetypes.xml`, layered
+    /// over the built-in tables.
+    ///
+    /// See [`MimeRegistry::load_tika`].
+    pub fn from_tika(contents: &str) -> Result<MimeRegistry, ParseTikaError> {
+        let mut registry = MimeRegistry::new();
+        registry.load_tika(contents)?;
+        Ok(registry)
+    }
+
+    /// Loads mime-type definitions in the format of Apache Tika's
+    /// `tika-mimetypes.xml` or `custom-mimetypes.xml`.
+    ///
+    /// Reads `alias`, `sub-class-of`, `glob` (including `isregex` patterns),
+    /// `magic` with its priority and nested `match` elements, and `root-XML`
+    /// rules. Types resolve to the existing `MimeType` variants where one
+    /// exists and become [`MimeType::Other`] otherwise. Magic rules join
+    /// [`MimeRegistry::from_bytes`] with their Tika priority, which uses the
+    /// same scale as the built-in signatures, and `root-XML` rules identify
+    /// XML documents by the name and namespace of their root element.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the XML is malformed, a type is not a valid media
+    /// type, or a `match` element has an unknown type or invalid value.
+    /// `regex` matches using syntax the built-in engine does not support
+    /// never match instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::MimeRegistry;
+    ///
+    /// let registry = MimeRegistry::from_tika(r#"
+    ///     <mime-info>
+    ///       <mime-type type="application/x-scene">
+    ///         <glob pattern="*.scene"/>
+    ///         <magic priority="60">
+    ///           <match value="SCENE" type="string" offset="0">
+    ///             <match value="0x02" type="byte" offset="8"/>
+    ///           </match>
+    ///         </magic>
+    ///       </mime-type>
+    ///       <mime-type type="application/x-plan+xml">
+    ///         <sub-class-of type="application/xml"/>
+    ///         <root-XML localName="plan" namespaceURI="urn:example:plan"/>
+    ///       </mime-type>
+    ///     </mime-info>
+    /// "#).unwrap();
+    ///
+    /// let scene = registry.from_ext("scene").unwrap();
+    /// assert_eq!(registry.from_bytes(b"SCENE\0\0\0\x02"), Some(scene));
+    ///
+    /// let plan = registry.from_bytes(br#"<?xml version="1.0"?><plan xmlns="urn:example:plan"/>"#);
+    /// assert_eq!(plan.unwrap().to_string(), "application/x-plan+xml");
+    /// ```
+    ///
+    /// A `regex` match must start within the offset range:
+    ///
+    /// ```
+    /// use mime_type::MimeRegistry;
+    ///
+    /// let registry = MimeRegistry::from_tika(r#"
+    ///     <mime-info>
+    ///       <mime-type type="text/x-scene-script">
+    ///         <magic>
+    ///           <match value="@scene v[0-9]+" type="regex" offset="0:4"/>
+    ///         </magic>
+    ///       </mime-type>
+    ///     </mime-info>
+    /// "#).unwrap();
+    ///
+    /// let script = registry.from_bytes(b"  @scene v2\n").unwrap();
+    /// assert_eq!(script.to_string(), "text/x-scene-script");
+    /// assert!(registry.from_bytes(b"\n\n\n\n\n\n@scene v2\n").is_none());
+    /// ```
+    pub fn load_tika(&mut self, contents: &str) -> Result<(), ParseTikaError> {
+        let mut reader = XmlReader::new(contents);
+        let mut seen = HashMap::new();
+        let mut current: Option<(String, MimeType)> = None;
+        let mut magic: Option<(u32, Vec<Matchlet>)> = None;
+        // Open `match` elements; `None` for ones that can never match
+        let mut open: Vec<Option<Matchlet>> = Vec::new();
+        let mut root_xml = RootXml { rules: Vec::new() };
+
+        loop {
+            let event = match reader.next_event() {
+                Ok(Some(event)) => event,
+                Ok(None) => break,
+                Err(reason) => {
+                    return Err(ParseTikaError {
+                        line: reader.line(),
+                        reason,
+                    });
+                }
+            };
+            let err = |reason| ParseTikaError {
+                line: reader.line(),
+                reason,
+            };
+            match &event {
+                Event::Start { name, empty, .. } => match name.as_str() {
+                    "mime-type" => {
+                        let mime = event.attribute("type").ok_or_else(|| err("missing type"))?;
+                        let mime_type = self
+                            .register(mime, &[])
+                            .map_err(|_| err("invalid media type"))?;
+                        current = Some((mime.to_string(), mime_type));
+                    }
+                    "alias" | "sub-class-of" | "glob" | "magic" | "root-XML" => {
+                        let (mime, mime_type) = current
+                            .clone()
+                            .ok_or_else(|| err("element outside mime-type"))?;
+                        match name.as_str() {
+                            "alias" => {
+                                let alias =
+                                    event.attribute("type").ok_or_else(|| err("missing type"))?;
+                                self.register_mime(alias, mime_type);
+                            }
+                            "sub-class-of" => {
+                                let parent =
+                                    event.attribute("type").ok_or_else(|| err("missing type"))?;
+                                self.register_subclass(&mime, parent);
+                            }
+                            "glob" => {
+                                let pattern = event
+                                    .attribute("pattern")
+                                    .ok_or_else(|| err("missing pattern"))?;
+                                if event.attribute("isregex") == Some("true") {
+                                    if let Ok(regex) = Regex::new(pattern, false) {
+                                        self.globs.push(Glob {
+                                            pattern: pattern.to_string(),
+                                            weight: GLOB_WEIGHT,
+                                            case_sensitive: true,
+                                            regex: Some(regex),
+                                            mime: mime_type,
+                                        });
+                                    }
+                                } else {
+                                    self.add_glob_line(
+                                        &mime,
+                                        pattern,
+                                        GLOB_WEIGHT,
+                                        false,
+                                        &mut seen,
+                                    )
+                                    .map_err(err)?;
+                                }
+                            }
+                            "magic" => {
+                                let priority = match event.attribute("priority") {
+                                    Some(priority) => {
+                                        priority.parse().map_err(|_| err("invalid priority"))?
+                                    }
+                                    None => BUILTIN_PRIORITY,
+                                };
+                                if !*empty {
+                                    magic = Some((priority, Vec::new()));
+                                }
+                            }
+                            _ => {
+                                root_xml.rules.push(RootRule {
+                                    local_name: event.attribute("localName").map(str::to_string),
+                                    namespace: event.attribute("namespaceURI").map(str::to_string),
+                                    mime: mime_type,
+                                });
+                            }
+                        }
+                    }
+                    "match" => {
+                        if magic.is_none() {
+                            return Err(err("match outside magic"));
+                        }
+                        let matchlet = parse_match(&event).map_err(err)?;
+                        if *empty {
+                            attach(&mut open, &mut magic, matchlet);
+                        } else {
+                            open.push(matchlet);
+                        }
+                    }
+                    _ => {}
+                },
+                Event::End { name } => match name.as_str() {
+                    "match" => {
+                        let matchlet = open.pop().ok_or_else(|| err("unmatched </match>"))?;
+                        attach(&mut open, &mut magic, matchlet);
+                    }
+                    "magic" => {
+                        if let (Some((priority, matchlets)), Some((_, mime))) =
+                            (magic.take(), &current)
+                        {
+                            self.add_magic(
+                                priority,
+                                Arc::new(MagicEntry {
+                                    mime: mime.clone(),
+                                    matchlets,
+                                }),
+                            );
+                        }
+                    }
+                    "mime-type" => current = None,
+                    _ => {}
+                },
+            }
+        }
+
+        if !root_xml.rules.is_empty() {
+            self.add_magic(ROOT_XML_PRIORITY, Arc::new(root_xml));
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when a Tika mime-types file is malformed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseTikaError {
+    line: usize,
+    reason: &'static str,
+}
+
+impl ParseTikaError {
+    /// Returns the 1-based line at which the error was detected.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl Display for ParseTikaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl Error for ParseTikaError {}
+
+/// Identifies XML documents by the name and namespace of their root element.
+#[derive(Debug)]
+struct RootXml {
+    rules: Vec<RootRule>,
+}
+
+#[derive(Debug)]
+struct RootRule {
+    local_name: Option<String>,
+    namespace: Option<String>,
+    mime: MimeType,
+}
+
+impl Sniffer for RootXml {
+    fn sniff(&self, bytes: &[u8]) -> Option<MimeType> {
+        let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+        let text = String::from_utf8_lossy(bytes);
+        if !text.trim_start().starts_with('<') {
+            return None;
+        }
+        let mut reader = XmlReader::new(&text);
+        let root = reader.next_event().ok()??;
+        let Event::Start { name, .. } = &root else {
+            return None;
+        };
+        let (prefix, local_name) = match name.split_once(':') {
+            Some((prefix, local_name)) => (Some(prefix), local_name),
+            None => (None, name.as_str()),
+        };
+        let namespace = match prefix {
+            Some(prefix) => root.attribute(&format!("xmlns:{}", prefix)),
+            None => root.attribute("xmlns"),
+        };
+        self.rules
+            .iter()
+            .find(|rule| {
+                rule.local_name
+                    .as_deref()
+                    .is_none_or(|name| name == local_name)
+                    && rule
+                        .namespace
+                        .as_deref()
+                        .is_none_or(|uri| Some(uri) == namespace)
+            })
+            .map(|rule| rule.mime.clone())
+    }
+}
+
+/// Adds a finished matchlet to the enclosing `match` or `magic` element.
+/// Matchlets that can never match are dropped along with their children.
+fn attach(
+    open: &mut [Option<Matchlet>],
+    magic: &mut Option<(u32, Vec<Matchlet>)>,
+    matchlet: Option<Matchlet>,
+) {
+    let Some(matchlet) = matchlet else {
+        return;
+    };
+    match open.last_mut() {
+        Some(Some(parent)) => parent.children.push(matchlet),
+        Some(None) => {}
+        None => {
+            if let Some((_, matchlets)) = magic {
+                matchlets.push(matchlet);
+            }
+        }
+    }
+}
+
+/// Parses a `<match>` element. Returns `None` for a `regex` match the
+/// built-in engine cannot compile.
+fn parse_match(event: &Event) -> Result<Option<Matchlet>, &'static str> {
+    let kind = event.attribute("type").unwrap_or("string");
+    let value = event.attribute("value").ok_or("missing value")?;
+    let mask = event.attribute("mask");
+    let offset = event.attribute("offset").unwrap_or("0");
+    let (start, end) = match offset.split_once(':') {
+        Some((start, end)) => (start, end),
+        None => (offset, offset),
+    };
+    let start: usize = start.trim().parse().map_err(|_| "invalid offset")?;
+    let end: usize = end.trim().parse().map_err(|_| "invalid offset")?;
+    if end < start {
+        return Err("invalid offset");
+    }
+
+    let number = |size: usize, big_endian: bool, value: &str| {
+        let value = parse_number(value).ok_or("invalid numeric value")?;
+        let bytes = value.to_be_bytes();
+        let mut field = bytes[bytes.len() - size..].to_vec();
+        if !big_endian {
+            field.reverse();
+        }
+        Ok::<_, &'static str>(field)
+    };
+    let numeric = |size, big_endian| {
+        Ok::<_, &'static str>(Pattern::Bytes {
+            value: number(size, big_endian, value)?,
+            mask: mask
+                .map(|mask| number(size, big_endian, mask))
+                .transpose()?,
+        })
+    };
+    let host_big = cfg!(target_endian = "big");
+    let pattern = match kind {
+        "string" => Pattern::Bytes {
+            value: decode_string(value)?,
+            mask: mask.map(decode_string).transpose()?,
+        },
+        "stringignorecase" => Pattern::IgnoreCase(decode_string(value)?),
+        "unicodeLE" => Pattern::Bytes {
+            value: value.encode_utf16().flat_map(u16::to_le_bytes).collect(),
+            mask: None,
+        },
+        "unicodeBE" => Pattern::Bytes {
+            value: value.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+            mask: None,
+        },
+        "byte" => numeric(1, true)?,
+        "big16" => numeric(2, true)?,
+        "little16" => numeric(2, false)?,
+        "host16" => numeric(2, host_big)?,
+        "big32" => numeric(4, true)?,
+        "little32" => numeric(4, false)?,
+        "host32" => numeric(4, host_big)?,
+        "regex" => match Regex::new(value, false) {
+            Ok(regex) => Pattern::Regex(regex),
+            Err(_) => return Ok(None),
+        },
+        _ => return Err("unknown match type"),
+    };
+    Ok(Some(Matchlet {
+        offset: start,
+        range: (end - start).saturating_add(1),
+        pattern,
+        children: Vec::new(),
+    }))
+}
+
+/// Decodes a Tika string value: `0x` followed by hex digits, or text with
+/// `\xHH`, octal, `\n`, `\r`, `\t` and `\\` escapes. Characters up to
+/// U+00FF stand for single bytes.
+fn decode_string(value: &str) -> Result<Vec<u8>, &'static str> {
+    if let Some(hex) = value.strip_prefix("0x") {
+        if hex.len() % 2 != 0 {
+            return Err("invalid hex string");
+        }
+        return (0..hex.len())
+            .step_by(2)
+            .map(|idx| {
+                hex.get(idx..idx + 2)
+                    .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                    .ok_or("invalid hex string")
+            })
+            .collect();
+    }
+
+    let mut out = Vec::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            match u8::try_from(c) {
+                Ok(byte) => out.push(byte),
+                Err(_) => out.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+            }
+            continue;
+        }
+        match chars.next().ok_or("trailing backslash")? {
+            'x' => {
+                let hex: String = (0..2).filter_map(|_| chars.next()).collect();
+                out.push(u8::from_str_radix(&hex, 16).map_err(|_| "invalid escape")?);
+            }
+            digit @ '0'..='7' => {
+                let mut octal = digit.to_string();
+                while octal.len() < 3
+                    && let Some(next) = chars.next_if(|c| ('0'..='7').contains(c))
+                {
+                    octal.push(next);
+                }
+                out.push(u16::from_str_radix(&octal, 8).map_err(|_| "invalid escape")? as u8);
+            }
+            'n' => out.push(b'\n'),
+            'r' => out.push(b'\r'),
+            't' => out.push(b'\t'),
+            other => match u8::try_from(other) {
+                Ok(byte) => out.push(byte),
+                Err(_) => return Err("invalid escape"),
+            },
+        }
+    }
+    Ok(out)
+}
+
+/// Parses a decimal, `0x` hexadecimal or `0` octal number.
+fn parse_number(value: &str) -> Option<u64> {
+    let value = value.trim();
+    if let Some(hex) = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        u64::from_str_radix(hex, 16).ok()
+    } else if value.len() > 1 && value.starts_with('0') {
+        u64::from_str_radix(&value[1..], 8).ok()
+    } else {
+        value.parse().ok()
+    }
+}
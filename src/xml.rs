@@ -0,0 +1,201 @@
+/// A minimal pull parser for the XML files the registry imports.
+///
+/// Only elements and their attributes are reported; text, comments,
+/// processing instructions, CDATA sections and the document type declaration
+/// are skipped. Attribute values have the predefined and numeric entities
+/// decoded. Namespaces are left to the caller.
+pub(crate) struct XmlReader<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+/// An element boundary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Event {
+    /// A start tag; `empty` is set for self-closing tags such as `<a/>`,
+    /// which produce no `End` event.
+    Start {
+        name: String,
+        attributes: Vec<(String, String)>,
+        empty: bool,
+    },
+    End {
+        name: String,
+    },
+}
+
+impl Event {
+    /// Returns the value of an attribute of a start tag.
+    pub(crate) fn attribute(&self, key: &str) -> Option<&str> {
+        match self {
+            Event::Start { attributes, .. } => attributes
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.as_str()),
+            Event::End { .. } => None,
+        }
+    }
+}
+
+impl<'a> XmlReader<'a> {
+    pub(crate) fn new(input: &'a str) -> Self {
+        XmlReader { input, pos: 0 }
+    }
+
+    /// Returns the 1-based line of the current position.
+    pub(crate) fn line(&self) -> usize {
+        1 + self.input[..self.pos]
+            .bytes()
+            .filter(|&b| b == b'\n')
+            .count()
+    }
+
+    /// Returns the next element boundary, or `None` at the end of input.
+    pub(crate) fn next_event(&mut self) -> Result<Option<Event>, &'static str> {
+        loop {
+            let Some(start) = self.input[self.pos..].find('<') else {
+                self.pos = self.input.len();
+                return Ok(None);
+            };
+            self.pos += start;
+            let rest = &self.input[self.pos..];
+            if rest.starts_with("<!--") {
+                self.skip_past("-->", "unterminated comment")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.skip_past("]]>", "unterminated CDATA section")?;
+            } else if rest.starts_with("<?") {
+                self.skip_past("?>", "unterminated processing instruction")?;
+            } else if rest.starts_with("<!") {
+                self.skip_declaration()?;
+            } else if let Some(rest) = rest.strip_prefix("</") {
+                let end = rest.find('>').ok_or("unterminated end tag")?;
+                let name = rest[..end].trim().to_string();
+                self.pos += 2 + end + 1;
+                return Ok(Some(Event::End { name }));
+            } else {
+                return self.start_tag().map(Some);
+            }
+        }
+    }
+
+    fn skip_past(&mut self, terminator: &str, error: &'static str) -> Result<(), &'static str> {
+        let end = self.input[self.pos..].find(terminator).ok_or(error)?;
+        self.pos += end + terminator.len();
+        Ok(())
+    }
+
+    /// Skips `<!DOCTYPE ...>`, including an internal subset in brackets.
+    fn skip_declaration(&mut self) -> Result<(), &'static str> {
+        let mut depth = 0;
+        for (idx, c) in self.input[self.pos..].char_indices() {
+            match c {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                '>' if depth == 0 => {
+                    self.pos += idx + 1;
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+        Err("unterminated declaration")
+    }
+
+    fn start_tag(&mut self) -> Result<Event, &'static str> {
+        self.pos += 1;
+        let name = self.name()?;
+        let mut attributes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let rest = &self.input[self.pos..];
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                return Ok(Event::Start {
+                    name,
+                    attributes,
+                    empty: true,
+                });
+            }
+            if rest.starts_with('>') {
+                self.pos += 1;
+                return Ok(Event::Start {
+                    name,
+                    attributes,
+                    empty: false,
+                });
+            }
+            if rest.is_empty() {
+                return Err("unterminated start tag");
+            }
+
+            let key = self.name()?;
+            self.skip_whitespace();
+            if !self.input[self.pos..].starts_with('=') {
+                return Err("expected '=' after attribute name");
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let quote = self.input[self.pos..]
+                .chars()
+                .next()
+                .filter(|c| *c == '"' || *c == '\'')
+                .ok_or("expected quoted attribute value")?;
+            self.pos += 1;
+            let len = self.input[self.pos..]
+                .find(quote)
+                .ok_or("unterminated attribute value")?;
+            let value = decode_entities(&self.input[self.pos..self.pos + len])?;
+            self.pos += len + 1;
+            attributes.push((key, value));
+        }
+    }
+
+    fn name(&mut self) -> Result<String, &'static str> {
+        let rest = &self.input[self.pos..];
+        let len = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '='))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err("expected name");
+        }
+        self.pos += len;
+        Ok(rest[..len].to_string())
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+}
+
+/// Decodes the predefined and numeric character entities.
+fn decode_entities(value: &str) -> Result<String, &'static str> {
+    let mut decoded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        let end = rest[start..].find(';').ok_or("unterminated entity")?;
+        let entity = &rest[start + 1..start + end];
+        let c = match entity {
+            "lt" => '<',
+            "gt" => '>',
+            "amp" => '&',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => {
+                let code = if let Some(hex) = entity.strip_prefix("#x") {
+                    u32::from_str_radix(hex, 16).ok()
+                } else if let Some(dec) = entity.strip_prefix('#') {
+                    dec.parse().ok()
+                } else {
+                    None
+                };
+                code.and_then(char::from_u32).ok_or("unknown entity")?
+            }
+        };
+        decoded.push(c);
+        rest = &rest[start + end + 1..];
+    }
+    decoded.push_str(rest);
+    Ok(decoded)
+}
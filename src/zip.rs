@@ -0,0 +1,269 @@
+use crate::{Archive, Document, MimeType};
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+
+const LOCAL_HEADER: &[u8] = b"PK\x03\x04";
+const CENTRAL_HEADER: &[u8] = b"PK\x01\x02";
+const END_OF_CENTRAL_DIRECTORY: &[u8] = b"PK\x05\x06";
+const ZIP64_END_OF_CENTRAL_DIRECTORY: &[u8] = b"PK\x06\x06";
+const ZIP64_LOCATOR: &[u8] = b"PK\x06\x07";
+
+/// Length of the end of central directory record without its comment.
+const EOCD_LEN: usize = 22;
+/// Length of the ZIP64 end of central directory record without extensions.
+const ZIP64_EOCD_LEN: usize = 56;
+/// Length of the ZIP64 end of central directory locator.
+const ZIP64_LOCATOR_LEN: usize = 20;
+/// Number of bytes read from the end of a file to find the central directory.
+const TAIL_LEN: u64 = 256 * 1024;
+
+/// The entries of a ZIP file, used to identify the format stored in it.
+///
+/// EPUB, OpenDocument and OpenRaster files declare their type in a leading
+/// `mimetype` entry. Other formats are recognized by the files they contain:
+/// `AndroidManifest.xml` for APK, `word/`, `xl/` and `ppt/` parts for Office
+/// Open XML, a `.fdseq` part for XPS, and `META-INF/MANIFEST.MF` for JAR.
+/// Office Open XML and XPS also require the `[Content_Types].xml` part that
+/// every Open Packaging Conventions file carries.
+///
+/// Entry names are read from the central directory at the end of the file
+/// when it is in the given bytes, and otherwise from the local file headers
+/// in the leading bytes.
+///
+/// # Examples
+///
+/// ```
+/// use mime_type::{Archive, MimeType, Zip};
+///
+/// // An empty entry named META-INF/MANIFEST.MF
+/// let mut jar = b"PK\x03\x04\x14\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x14\0\0\0".to_vec();
+/// jar.extend_from_slice(b"META-INF/MANIFEST.MF");
+/// let zip = Zip::parse(&jar).unwrap();
+/// assert_eq!(zip.entries(), &["META-INF/MANIFEST.MF"]);
+/// assert_eq!(zip.mime_type(), MimeType::Archive(Archive::Jar));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Zip {
+    entries: Vec<String>,
+    mimetype: Option<String>,
+    complete: bool,
+}
+
+impl Zip {
+    /// Parses a ZIP file, or its leading bytes.
+    ///
+    /// Returns `None` if the bytes neither start with a ZIP entry nor end
+    /// with a central directory.
+    pub fn parse(bytes: &[u8]) -> Option<Zip> {
+        Zip::from_parts(bytes, bytes)
+    }
+
+    /// Parses the leading bytes and the trailing bytes of a ZIP file.
+    pub(crate) fn from_parts(head: &[u8], tail: &[u8]) -> Option<Zip> {
+        let (entries, complete) = match central_directory(tail) {
+            Some(entries) => (entries, true),
+            None if head.starts_with(LOCAL_HEADER) => (local_entries(head), false),
+            None => return None,
+        };
+        Some(Zip {
+            entries,
+            mimetype: stored_mimetype(head),
+            complete,
+        })
+    }
+
+    /// Returns the entry names, in directory order.
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Returns the contents of a leading, uncompressed `mimetype` entry.
+    pub fn mimetype(&self) -> Option<&str> {
+        self.mimetype.as_deref()
+    }
+
+    /// Returns `true` if the entries were read from the central directory and
+    /// so list the whole archive.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Maps the entries to the format stored in the ZIP file, or
+    /// `Archive::Zip` if none is recognized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mime_type::{Archive, Document, MimeType, Zip};
+    ///
+    /// // Empty stored entries with the given names
+    /// let zip = |names: &[&str]| {
+    ///     let mut bytes = Vec::new();
+    ///     for name in names {
+    ///         bytes.extend_from_slice(b"PK\x03\x04\x14\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0");
+    ///         bytes.extend_from_slice(&(name.len() as u16).to_le_bytes());
+    ///         bytes.extend_from_slice(b"\0\0");
+    ///         bytes.extend_from_slice(name.as_bytes());
+    ///     }
+    ///     Zip::parse(&bytes).unwrap().mime_type()
+    /// };
+    /// let docx = zip(&["[Content_Types].xml", "word/document.xml"]);
+    /// assert_eq!(docx, MimeType::Document(Document::Docx));
+    ///
+    /// // Without [Content_Types].xml it is not an Office Open XML file
+    /// let plain = zip(&["word/document.xml"]);
+    /// assert_eq!(plain, MimeType::Archive(Archive::Zip));
+    /// ```
+    pub fn mime_type(&self) -> MimeType {
+        if let Some(mime) = self.mimetype.as_deref().and_then(crate::known_from_mime) {
+            return mime;
+        }
+
+        let has = |name: &str| self.entries.iter().any(|entry| entry == name);
+        let has_prefix = |prefix: &str| self.entries.iter().any(|entry| entry.starts_with(prefix));
+        let opc = has("[Content_Types].xml");
+        if has("AndroidManifest.xml") || has("classes.dex") {
+            MimeType::Archive(Archive::Apk)
+        } else if opc && has_prefix("word/") {
+            MimeType::Document(Document::Docx)
+        } else if opc && has_prefix("xl/") {
+            MimeType::Document(Document::Xlsx)
+        } else if opc && has_prefix("ppt/") {
+            MimeType::Document(Document::Pptx)
+        } else if opc && self.entries.iter().any(|entry| entry.ends_with(".fdseq")) {
+            MimeType::Document(Document::Xps)
+        } else if has("META-INF/MANIFEST.MF") {
+            MimeType::Archive(Archive::Jar)
+        } else {
+            MimeType::Archive(Archive::Zip)
+        }
+    }
+}
+
+/// Identifies the format stored in a ZIP file, reading its central directory
+/// from the end of the file.
+pub(crate) fn detect_file(file: &mut File, head: &[u8]) -> io::Result<Option<MimeType>> {
+    let len = file.metadata()?.len();
+    let mut tail = Vec::new();
+    file.seek(SeekFrom::Start(len.saturating_sub(TAIL_LEN)))?;
+    file.take(TAIL_LEN).read_to_end(&mut tail)?;
+    Ok(Zip::from_parts(head, &tail).map(|zip| zip.mime_type()))
+}
+
+fn u16_le(bytes: &[u8], offset: usize) -> Option<usize> {
+    let b = bytes.get(offset..offset + 2)?;
+    Some(usize::from(u16::from_le_bytes([b[0], b[1]])))
+}
+
+fn u32_le(bytes: &[u8], offset: usize) -> Option<u64> {
+    let b = bytes.get(offset..offset + 4)?;
+    Some(u64::from(u32::from_le_bytes([b[0], b[1], b[2], b[3]])))
+}
+
+fn u64_le(bytes: &[u8], offset: usize) -> Option<u64> {
+    let b = bytes.get(offset..offset + 8)?;
+    Some(u64::from_le_bytes([
+        b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7],
+    ]))
+}
+
+/// Reads the entry names from the central directory at the end of `bytes`.
+///
+/// The directory is located relative to the end of central directory record,
+/// so data prepended to the archive does not matter.
+fn central_directory(bytes: &[u8]) -> Option<Vec<String>> {
+    let last = bytes.len().checked_sub(EOCD_LEN)?;
+    let eocd = (0..=last)
+        .rev()
+        .take(usize::from(u16::MAX) + 1)
+        .find(|&pos| {
+            bytes[pos..].starts_with(END_OF_CENTRAL_DIRECTORY)
+                && u16_le(bytes, pos + 20) == Some(bytes.len() - pos - EOCD_LEN)
+        })?;
+
+    let mut end = eocd;
+    let mut size = u32_le(bytes, eocd + 12)?;
+    if size == u64::from(u32::MAX) {
+        let locator = eocd.checked_sub(ZIP64_LOCATOR_LEN)?;
+        let record = locator.checked_sub(ZIP64_EOCD_LEN)?;
+        if !bytes[locator..].starts_with(ZIP64_LOCATOR)
+            || !bytes[record..].starts_with(ZIP64_END_OF_CENTRAL_DIRECTORY)
+        {
+            return None;
+        }
+        end = record;
+        size = u64_le(bytes, record + 40)?;
+    }
+    let start = end.checked_sub(usize::try_from(size).ok()?)?;
+
+    let directory = &bytes[start..end];
+    if !directory.is_empty() && !directory.starts_with(CENTRAL_HEADER) {
+        return None;
+    }
+    let mut entries = Vec::new();
+    let mut pos = 0;
+    while directory
+        .get(pos..)
+        .is_some_and(|rest| rest.starts_with(CENTRAL_HEADER))
+    {
+        let name_len = u16_le(directory, pos + 28)?;
+        let extra_len = u16_le(directory, pos + 30)?;
+        let comment_len = u16_le(directory, pos + 32)?;
+        let name = directory.get(pos + 46..pos + 46 + name_len)?;
+        entries.push(String::from_utf8_lossy(name).into_owned());
+        pos += 46 + name_len + extra_len + comment_len;
+    }
+    Some(entries)
+}
+
+/// Reads the entry names from the local file headers in `bytes`.
+///
+/// When an entry's size is deferred to a data descriptor, the next header is
+/// found by searching for its signature.
+fn local_entries(bytes: &[u8]) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut pos = 0;
+    while let Some(header) = bytes.get(pos..pos + 30)
+        && header.starts_with(LOCAL_HEADER)
+    {
+        let flags = u16_le(header, 6).unwrap_or_default();
+        let size = u32_le(header, 18).unwrap_or_default() as usize;
+        let name_len = u16_le(header, 26).unwrap_or_default();
+        let extra_len = u16_le(header, 28).unwrap_or_default();
+        let Some(name) = bytes.get(pos + 30..pos + 30 + name_len) else {
+            break;
+        };
+        entries.push(String::from_utf8_lossy(name).into_owned());
+
+        let data = pos + 30 + name_len + extra_len;
+        pos = if flags & 0x08 == 0 {
+            data.saturating_add(size)
+        } else {
+            let Some(next) = bytes
+                .get(data..)
+                .and_then(|rest| rest.windows(4).position(|window| window == LOCAL_HEADER))
+            else {
+                break;
+            };
+            data + next
+        };
+    }
+    entries
+}
+
+/// Returns the contents of a `mimetype` entry stored uncompressed at the
+/// start of the archive.
+fn stored_mimetype(bytes: &[u8]) -> Option<String> {
+    if !bytes.starts_with(LOCAL_HEADER)
+        || u16_le(bytes, 8)? != 0
+        || u16_le(bytes, 26)? != 8
+        || bytes.get(30..38) != Some(b"mimetype")
+    {
+        return None;
+    }
+    let size = u32_le(bytes, 18)? as usize;
+    let extra_len = u16_le(bytes, 28)?;
+    let start = 38 + extra_len;
+    let value = bytes.get(start..start + size)?;
+    Some(String::from_utf8_lossy(value).trim().to_string())
+}